@@ -20,4 +20,7 @@ pub enum ContractError {
 
     #[error("Not token owner")]
     NotTokenOwner {},
+
+    #[error("Address is blocked")]
+    Blocked {},
 } 
\ No newline at end of file
@@ -18,22 +18,41 @@ pub fn create_contract_version(name: &str, version: &str) -> String {
 pub use role_manager::{
     // Main entry points
     instantiate as role_manager_instantiate,
+    migrate as role_manager_migrate,
+    reply as role_manager_reply,
     execute as role_manager_execute,
     query as role_manager_query,
     // Execute functions
-    grantRole, revokeRole, renounceRole, authorizeFanAssigner, assignFanRole,
+    grantRole, extendRole, revokeRole, renounceRole, authorizeFanAssigner, assignFanRole, setRoleAdmin,
+    registerRole, deprecateRole, proposeAdmin, acceptAdmin,
+    revokeFanAssigner, addHook, removeHook, clearExpiredRole, updateSecurity, approveProposal, executeProposal, cancelProposal,
+    grantScopedRole, revokeScopedRole, has_scoped_role, setPermission, removePermission, can_perform,
+    block, unblock, is_blocked, ACTION_BLOCK, applyForRole, approveApplication, rejectApplication,
+    registerPlatformContract, removePlatformContract, pauseAll, unpauseAll, ACTION_PAUSE_ALL,
+    setBadgeUri, badge_token_id, registerSessionKey, revokeSessionKey, is_session_authorized, registerVoucherSigner, removeVoucherSigner, claimRole, voucher_digest, batchGrantRoles, batchRevokeRoles, updateConfig as role_manager_update_config,
     pause as role_manager_pause, unpause as role_manager_unpause,
     // Query functions
-    has_role,
+    has_role, get_role_admin,
     // Constants
     DEFAULT_ADMIN_ROLE, FAN_ROLE, ORGANIZER_ROLE, ARTIST_ROLE, 
-    BRAND_ROLE, MODERATOR_ROLE, FAN_ASSIGNER_ROLE,
+    BRAND_ROLE, MODERATOR_ROLE, FAN_ASSIGNER_ROLE, GUARDIAN_ROLE,
+    PAUSE_SCOPE_GRANTS, PAUSE_SCOPE_FAN_ASSIGNMENT,
     // Storage
     PAUSED as ROLE_MANAGER_PAUSED,
     // Response types
-    RolesResponse, BoolResponse as RoleBoolResponse,
+    RolesResponse, BoolResponse as RoleBoolResponse, RoleAdminResponse,
+    RoleMembersResponse, RoleMemberCountResponse, RoleGrantResponse, RoleGrant,
+    RegisteredRolesResponse, RegisteredRole, RoleInfo,
+    PendingAdminResponse, PendingAdmin,
+    // Types
+    Config as RoleManagerConfig, RoleAssignment, FanAssignerAllowance, FanAssignerAllowanceResponse,
+    RoleHookMsg, HooksResponse, PlatformContract, PlatformContractKind, PlatformPauseMsg, PlatformQuery,
+    PlatformContractStatus, PlatformStatusResponse, Proposal, ProposalAction, ProposalStatus, ProposalsResponse,
+    AuditEntry, AuditLogResponse, Permission, PermissionsResponse, BlockEntry, BlockEntryResponse, SessionKey, SessionKeysResponse, Badge, NftInfoResponse, OwnerOfResponse as BadgeOwnerOfResponse, TokensResponse as BadgeTokensResponse,
+    Application, ApplicationStatus, ApplicationResponse, ApplicationsResponse, KeyType, RoleVoucher, VoucherSigner, VoucherSignerInfo, VoucherSignersResponse, VoucherClaimsResponse,
     // Messages
     InstantiateMsg as RoleManagerInstantiateMsg,
+    MigrateMsg as RoleManagerMigrateMsg,
     ExecuteMsg as RoleManagerExecuteMsg,
     QueryMsg as RoleManagerQueryMsg,
 };
@@ -63,6 +82,7 @@ pub use profile_nft_minter::{
     InstantiateMsg as ProfileNFTInstantiateMsg,
     ExecuteMsg as ProfileNFTExecuteMsg,
     QueryMsg as ProfileNFTQueryMsg,
+    RoleManagerQuery as ProfileRoleManagerQuery,
     // Structs
     Config as ProfileConfig,
 };
@@ -73,30 +93,35 @@ pub use tribe_controller::{
     instantiate as tribe_controller_instantiate,
     execute as tribe_controller_execute,
     query as tribe_controller_query,
+    migrate as tribe_controller_migrate,
     // Execute functions
     createTribe, updateTribe, updateTribeConfig, joinTribe,
     requestToJoinTribe, approveMember, rejectMember, banMember,
     joinTribeWithCode, createInviteCode, requestMerge,
     approveMerge, executeMerge, revokeInviteCode, cancelMerge,
+    depositTreasury, withdrawTreasury, updateProtocolFee, executeOnBehalf as tribe_execute_on_behalf, roleChanged as tribe_role_changed, setPaused as tribe_set_paused,
     // Query functions
     getTribeAdmin, getTribeWhitelist, isAddressWhitelisted,
     getMemberStatus, getTribeConfigView, getMemberCount,
     getUserTribes, getInviteCodeStatus, getMergeRequest, getTribeDetails,
-    is_tribe_member, is_tribe_admin_check, is_tribe_member_with_status,
+    getTreasury, getTreasuryHistory, getProtocolFee, listMembers, listPendingRequests,
+    is_tribe_member, is_tribe_admin_check, is_tribe_member_with_status, member_status,
     is_whitelisted as tribe_is_whitelisted,
     // Types and Enums
     JoinType, MemberStatus, NFTType, NFTRequirement,
     InviteCode, MergeRequest, TribeConfigView, Config as TribeConfig,
-    TribeMeta, TribeData, TribeMember, TribeDetailsView,
+    TribeMeta, TribeData, TribeMember, TribeDetailsView, TreasuryEntry, TreasuryEntryKind,
     // Response types
     AdminResponse, WhitelistResponse, BoolResponse as TribeBoolResponse,
     MemberStatusResponse, TribeConfigViewResponse, MemberCountResponse,
     UserTribesResponse, InviteCodeStatusResponse, MergeRequestResponse,
+    TreasuryResponse, TreasuryHistoryResponse, ProtocolFeeResponse, MembersResponse,
     // Query enums
     RoleManagerQuery as TribeRoleManagerQuery, 
     Erc721Query, Erc1155Query,
     // Messages
     InstantiateMsg as TribeControllerInstantiateMsg,
+    MigrateMsg as TribeControllerMigrateMsg,
     ExecuteMsg as TribeControllerExecuteMsg,
     QueryMsg as TribeControllerQueryMsg,
     // Other response types
@@ -113,6 +138,8 @@ pub use post_minter::{
     deletePost, reportPost, authorizeViewer, setTribeEncryptionKey,
     interactWithPost, createBatchPosts, setPostTypeCooldown, updatePost,
     pause as post_pause, unpause as post_unpause,
+    executeOnBehalf as post_execute_on_behalf,
+    query as post_minter_query, is_paused as post_is_paused,
     // Query functions 
     canViewPost, verifyPostAccess, getInteractionCount, getPostReplies,
     query_get_post, validateMetadata, getPostTypeCooldown, getRemainingCooldown,
@@ -191,7 +218,7 @@ mod tests {
         assert_eq!(response.attributes.len(), 2);
         
         // Check that admin role was granted
-        let has_admin = has_role(deps.as_ref().storage, &Addr::unchecked("creator"), DEFAULT_ADMIN_ROLE).unwrap();
+        let has_admin = has_role(deps.as_ref().storage, &mock_env(), &Addr::unchecked("creator"), DEFAULT_ADMIN_ROLE).unwrap();
         assert!(has_admin);
         println!("Role manager initialization test passed!");
     }
@@ -1,3 +1,6 @@
+// Handlers keep the camelCase names of the Solidity contracts they port
+#![allow(non_snake_case)]
+
 use cosmwasm_std::{
     to_json_binary, from_json, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Addr, Uint128, Order,
     entry_point, Storage, Attribute, Event, SubMsg, CosmosMsg, WasmMsg, QueryRequest, WasmQuery,
@@ -14,6 +17,21 @@ use std::time::{SystemTime, UNIX_EPOCH};
 pub const DEFAULT_ADMIN_ROLE: &str = "DEFAULT_ADMIN_ROLE";
 pub const RATE_LIMIT_MANAGER_ROLE: &str = "RATE_LIMIT_MANAGER_ROLE";
 pub const PROJECT_CREATOR_ROLE: &str = "PROJECT_CREATOR_ROLE";
+// Global roles mirrored into ROLES by RoleChanged; DEFAULT_ADMIN_ROLE here is local to this contract
+const CACHED_ROLES: [&str; 2] = [RATE_LIMIT_MANAGER_ROLE, PROJECT_CREATOR_ROLE];
+
+// Actions checked against role_manager's permission matrix
+pub const ACTION_SET_COOLDOWN: &str = "post_minter.set_cooldown";
+pub const ACTION_BYPASS_COOLDOWN: &str = "post_minter.bypass_cooldown";
+pub const ACTION_MODERATE: &str = "post_minter.moderate";
+pub const ACTION_PROJECT_UPDATE: &str = "post_minter.project_update";
+
+// Actions a session key can be granted in role_manager
+pub const SESSION_ACTION_POST: &str = "post_minter.post";
+pub const SESSION_ACTION_REPLY: &str = "post_minter.reply";
+pub const SESSION_ACTION_INTERACT: &str = "post_minter.interact";
+pub const SESSION_ACTION_EDIT: &str = "post_minter.edit";
+pub const SESSION_ACTION_REPORT: &str = "post_minter.report";
 
 // Error constants
 const ERROR_INVALID_METADATA: &str = "Invalid metadata";
@@ -220,6 +238,18 @@ pub enum ExecuteMsg {
     },
     Pause {},
     Unpause {},
+    // Hook sent by role_manager whenever a role is granted or removed
+    RoleChanged {
+        user: String,
+        role: String,
+        scope: Option<String>,
+        granted: bool,
+    },
+    // Sent by a session key registered in role_manager, executing `msg` as `owner`
+    ExecuteOnBehalf {
+        owner: String,
+        msg: Box<ExecuteMsg>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -302,6 +332,7 @@ pub enum QueryMsg {
         offset: u64,
         limit: u64,
     },
+    IsPaused {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -368,6 +399,24 @@ pub enum RoleManagerQuery {
         user: String,
         role: String,
     },
+    HasScopedRole {
+        user: String,
+        role: String,
+        scope: String,
+    },
+    CanPerform {
+        user: String,
+        action: String,
+        scope: Option<String>,
+    },
+    IsBlocked {
+        user: String,
+    },
+    IsSessionAuthorized {
+        owner: String,
+        session: String,
+        action: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -503,9 +552,9 @@ fn grant_role(storage: &mut dyn Storage, address: Addr, role: String) -> StdResu
     ROLES.save(storage, &key, &true)
 }
 
-fn has_role(storage: &dyn Storage, address: &Addr, role: &str) -> bool {
+fn revoke_role(storage: &mut dyn Storage, address: &Addr, role: &str) {
     let key = format!("{}:{}", address, role);
-    ROLES.may_load(storage, &key).unwrap_or(None).unwrap_or(false)
+    ROLES.remove(storage, &key)
 }
 
 fn get_config(storage: &dyn Storage) -> StdResult<Config> {
@@ -531,6 +580,82 @@ fn is_tribe_member(deps: Deps, tribe_id: u64, member: &Addr) -> StdResult<bool>
     }
 }
 
+// Ask role_manager whether `addr` may perform `action`, optionally within a scope such as "tribe:5"
+fn can_perform(deps: Deps, addr: &Addr, action: &str, scope: Option<String>) -> StdResult<bool> {
+    let config = get_config(deps.storage)?;
+    
+    let query_msg = to_json_binary(&RoleManagerQuery::CanPerform {
+        user: addr.to_string(),
+        action: action.to_string(),
+        scope,
+    })?;
+    
+    let query_result: StdResult<BoolResponse> = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: config.role_manager.to_string(),
+        msg: query_msg,
+    }));
+    
+    match query_result {
+        Ok(response) => Ok(response.result),
+        Err(_) => Ok(false),
+    }
+}
+
+// Check the platform-wide blocklist in role_manager; an unanswered query counts as blocked
+fn is_blocked(deps: Deps, addr: &Addr) -> StdResult<bool> {
+    let config = get_config(deps.storage)?;
+    
+    let query_msg = to_json_binary(&RoleManagerQuery::IsBlocked {
+        user: addr.to_string(),
+    })?;
+    
+    let query_result: StdResult<BoolResponse> = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: config.role_manager.to_string(),
+        msg: query_msg,
+    }));
+    
+    match query_result {
+        Ok(response) => Ok(response.result),
+        Err(_) => Ok(true),
+    }
+}
+
+// Check that `session` holds a live role_manager session key of `owner` for `action`
+fn is_session_authorized(deps: Deps, owner: &Addr, session: &Addr, action: &str) -> StdResult<bool> {
+    let config = get_config(deps.storage)?;
+    
+    let query_msg = to_json_binary(&RoleManagerQuery::IsSessionAuthorized {
+        owner: owner.to_string(),
+        session: session.to_string(),
+        action: action.to_string(),
+    })?;
+    
+    let query_result: StdResult<BoolResponse> = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: config.role_manager.to_string(),
+        msg: query_msg,
+    }));
+    
+    match query_result {
+        Ok(response) => Ok(response.result),
+        Err(_) => Ok(false),
+    }
+}
+
+// Session action covering `msg`; admin and hook messages cannot be delegated
+fn session_action(msg: &ExecuteMsg) -> Option<&'static str> {
+    match msg {
+        ExecuteMsg::CreatePost { .. }
+        | ExecuteMsg::CreateEncryptedPost { .. }
+        | ExecuteMsg::CreateSignatureGatedPost { .. }
+        | ExecuteMsg::CreateBatchPosts { .. } => Some(SESSION_ACTION_POST),
+        ExecuteMsg::CreateReply { .. } => Some(SESSION_ACTION_REPLY),
+        ExecuteMsg::InteractWithPost { .. } => Some(SESSION_ACTION_INTERACT),
+        ExecuteMsg::UpdatePost { .. } | ExecuteMsg::DeletePost { .. } => Some(SESSION_ACTION_EDIT),
+        ExecuteMsg::ReportPost { .. } => Some(SESSION_ACTION_REPORT),
+        _ => None,
+    }
+}
+
 fn is_post_creator(deps: Deps, post_id: u64, addr: &Addr) -> StdResult<bool> {
     match POSTS.may_load(deps.storage, &u64_to_key(post_id))? {
         Some(post) => Ok(post.creator == *addr),
@@ -571,6 +696,12 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
+    // Only pause control and role_manager hooks get through while paused
+    let allowed_while_paused = matches!(msg, ExecuteMsg::Pause {} | ExecuteMsg::Unpause {} | ExecuteMsg::RoleChanged { .. });
+    if !allowed_while_paused && is_paused(deps.storage)? {
+        return Err(cosmwasm_std::StdError::generic_err("Contract is paused"));
+    }
+    
     match msg {
         ExecuteMsg::CreatePost {
             tribe_id,
@@ -657,6 +788,12 @@ pub fn execute(
         ExecuteMsg::Unpause {} => {
             unpause(deps, info)
         },
+        ExecuteMsg::RoleChanged { user, role, scope, granted } => {
+            roleChanged(deps, info, user, role, scope, granted)
+        },
+        ExecuteMsg::ExecuteOnBehalf { owner, msg } => {
+            executeOnBehalf(deps, env, info, owner, *msg)
+        },
     }
 }
 
@@ -670,6 +807,11 @@ pub fn createPost(
     collectible_contract: Option<String>,
     collectible_id: u64,
 ) -> StdResult<Response> {
+    // Blocked addresses cannot post
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
+    }
+    
     // Verify the sender is a tribe member
     if !is_tribe_member(deps.as_ref(), tribe_id, &info.sender)? {
         return Err(cosmwasm_std::StdError::generic_err("Not tribe member"));
@@ -678,8 +820,8 @@ pub fn createPost(
     // Parse metadata to determine post type
     let post_type = determine_post_type(&metadata)?;
     
-    // Check cooldown unless user has rate limit manager role
-    if !has_role(deps.storage, &info.sender, RATE_LIMIT_MANAGER_ROLE) {
+    // Check cooldown unless user may bypass it
+    if !can_perform(deps.as_ref(), &info.sender, ACTION_BYPASS_COOLDOWN, None)? {
         let current_time = env.block.time.seconds();
         if !check_cooldown(deps.storage, &info.sender, &post_type, current_time)? {
             return Err(cosmwasm_std::StdError::generic_err("Cooldown active"));
@@ -753,6 +895,11 @@ pub fn createReply(
     collectible_contract: Option<String>,
     collectible_id: u64,
 ) -> StdResult<Response> {
+    // Blocked addresses cannot post
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
+    }
+    
     // Get next post ID to validate parent post ID
     let next_post_id: u64 = NEXT_POST_ID.load(deps.storage)?;
     if parent_post_id >= next_post_id {
@@ -773,7 +920,7 @@ pub fn createReply(
     
     // Check cooldown 
     let current_time = env.block.time.seconds();
-    if !has_role(deps.storage, &info.sender, RATE_LIMIT_MANAGER_ROLE) {
+    if !can_perform(deps.as_ref(), &info.sender, ACTION_BYPASS_COOLDOWN, None)? {
         if !check_cooldown(deps.storage, &info.sender, &PostType::TEXT, current_time)? {
             return Err(cosmwasm_std::StdError::generic_err("Cooldown active"));
         }
@@ -847,6 +994,11 @@ pub fn createEncryptedPost(
     encryption_key_hash: String,
     access_signer: String,
 ) -> StdResult<Response> {
+    // Blocked addresses cannot post
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
+    }
+    
     // Check if tribe member
     if !is_tribe_member(deps.as_ref(), tribe_id, &info.sender)? {
         return Err(cosmwasm_std::StdError::generic_err("Not tribe member"));
@@ -867,7 +1019,7 @@ pub fn createEncryptedPost(
     
     // Check cooldown
     let current_time = env.block.time.seconds();
-    if !has_role(deps.storage, &info.sender, RATE_LIMIT_MANAGER_ROLE) {
+    if !can_perform(deps.as_ref(), &info.sender, ACTION_BYPASS_COOLDOWN, None)? {
         if !check_cooldown(deps.storage, &info.sender, &PostType::ENCRYPTED, current_time)? {
             return Err(cosmwasm_std::StdError::generic_err("Cooldown active"));
         }
@@ -928,6 +1080,11 @@ pub fn createSignatureGatedPost(
     collectible_contract: String,
     collectible_id: u64,
 ) -> StdResult<Response> {
+    // Blocked addresses cannot post
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
+    }
+    
     // Check if tribe member
     if !is_tribe_member(deps.as_ref(), tribe_id, &info.sender)? {
         return Err(cosmwasm_std::StdError::generic_err("Not tribe member"));
@@ -972,7 +1129,7 @@ pub fn createSignatureGatedPost(
     
     // Check cooldown
     let current_time = env.block.time.seconds();
-    if !has_role(deps.storage, &info.sender, RATE_LIMIT_MANAGER_ROLE) {
+    if !can_perform(deps.as_ref(), &info.sender, ACTION_BYPASS_COOLDOWN, None)? {
         if !check_cooldown(deps.storage, &info.sender, &PostType::TEXT, current_time)? {
             return Err(cosmwasm_std::StdError::generic_err("Cooldown active"));
         }
@@ -1022,13 +1179,15 @@ pub fn deletePost(
     info: MessageInfo,
     post_id: u64,
 ) -> StdResult<Response> {
-    // Check if post exists and user is creator
-    if !is_post_creator(deps.as_ref(), post_id, &info.sender)? {
+    // Creators may delete their own posts; moderators any post in their tribe
+    let post_data = POSTS.load(deps.storage, &u64_to_key(post_id))?;
+    if post_data.creator != info.sender
+        && !can_perform(deps.as_ref(), &info.sender, ACTION_MODERATE, Some(format!("tribe:{}", post_data.tribe_id)))?
+    {
         return Err(cosmwasm_std::StdError::generic_err("Not post creator"));
     }
     
     // Check if post is already deleted
-    let post_data = POSTS.load(deps.storage, &u64_to_key(post_id))?;
     
     if post_data.is_deleted {
         return Err(cosmwasm_std::StdError::generic_err("Post deleted"));
@@ -1196,6 +1355,34 @@ pub fn interactWithPost(
         .add_attribute("interaction_type", serialize_interaction_type(&interaction_type)))
 }
 
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::CanViewPost { post_id, viewer } => to_json_binary(&BoolResponse { result: canViewPost(deps, post_id, viewer)? }),
+        QueryMsg::VerifyPostAccess { post_id, viewer, signature } => {
+            to_json_binary(&BoolResponse { result: verifyPostAccess(deps, post_id, viewer, signature)? })
+        },
+        QueryMsg::GetInteractionCount { post_id, interaction_type } => to_json_binary(&getInteractionCount(deps, post_id, interaction_type)?),
+        QueryMsg::GetPostReplies { post_id } => to_json_binary(&getPostReplies(deps, post_id)?),
+        QueryMsg::GetPost { post_id } => to_json_binary(&query_get_post(deps, post_id)?),
+        QueryMsg::ValidateMetadata { metadata, post_type } => to_json_binary(&BoolResponse { result: validateMetadata(&metadata, &post_type) }),
+        QueryMsg::GetPostTypeCooldown { post_type } => to_json_binary(&getPostTypeCooldown(deps, post_type)?),
+        QueryMsg::GetRemainingCooldown { user, post_type } => to_json_binary(&getRemainingCooldown(deps, env, user, post_type)?),
+        QueryMsg::GetBatchPostingLimits {} => to_json_binary(&getBatchPostingLimits()?),
+        QueryMsg::GetPostsByTribe { tribe_id, offset, limit } => to_json_binary(&getPostsByTribe(deps, tribe_id, offset, limit)?),
+        QueryMsg::GetPostsByUser { user, offset, limit } => to_json_binary(&getPostsByUser(deps, user, offset, limit)?),
+        QueryMsg::GetPostsByTribeAndUser { tribe_id, user, offset, limit } => {
+            to_json_binary(&getPostsByTribeAndUser(deps, tribe_id, user, offset, limit)?)
+        },
+        QueryMsg::GetFeedForUser { user, offset, limit } => to_json_binary(&getFeedForUser(deps, user, offset, limit)?),
+        QueryMsg::IsPaused {} => to_json_binary(&BoolResponse { result: is_paused(deps.storage)? }),
+        _ => Err(cosmwasm_std::StdError::generic_err("Unsupported query")),
+    }
+}
+
+pub fn is_paused(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(PAUSED.may_load(storage)?.unwrap_or(false))
+}
+
 pub fn canViewPost(deps: Deps, post_id: u64, viewer: String) -> StdResult<bool> {
     // Convert viewer string to Addr
     let viewer_addr = deps.api.addr_validate(&viewer)?;
@@ -1550,8 +1737,8 @@ fn _validateProjectUpdatePermissions(
     info: &MessageInfo,
     metadata: &str
 ) -> StdResult<bool> {
-    // Check if user has project creator role
-    if !has_role(deps.storage, &info.sender, PROJECT_CREATOR_ROLE) {
+    // Check if user may post project updates
+    if !can_perform(deps, &info.sender, ACTION_PROJECT_UPDATE, None)? {
         return Ok(false);
     }
     
@@ -1592,6 +1779,11 @@ pub fn createBatchPosts(
     tribe_id: u64,
     posts: Vec<BatchPostData>,
 ) -> StdResult<Response> {
+    // Blocked addresses cannot post
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
+    }
+    
     // Check if batch size is within limits
     if posts.len() > MAX_BATCH_POSTS as usize {
         return Err(cosmwasm_std::StdError::generic_err(
@@ -1678,8 +1870,8 @@ pub fn setPostTypeCooldown(
     post_type: PostType,
     cooldown: u64,
 ) -> StdResult<Response> {
-    // Check if caller has rate limit manager role
-    if !has_role(deps.storage, &info.sender, RATE_LIMIT_MANAGER_ROLE) {
+    // Check if caller may manage cooldowns
+    if !can_perform(deps.as_ref(), &info.sender, ACTION_SET_COOLDOWN, None)? {
         return Err(cosmwasm_std::StdError::generic_err("Unauthorized"));
     }
     
@@ -1738,9 +1930,9 @@ pub fn pause(
     deps: DepsMut,
     info: MessageInfo,
 ) -> StdResult<Response> {
-    // Check if caller has admin role
+    // Owner, or role_manager relaying a platform-wide pause
     let config = get_config(deps.storage)?;
-    if info.sender != config.owner {
+    if info.sender != config.owner && info.sender != config.role_manager {
         return Err(cosmwasm_std::StdError::generic_err("Unauthorized"));
     }
     
@@ -1757,9 +1949,9 @@ pub fn unpause(
     deps: DepsMut,
     info: MessageInfo,
 ) -> StdResult<Response> {
-    // Check if caller has admin role
+    // Owner, or role_manager relaying a platform-wide unpause
     let config = get_config(deps.storage)?;
-    if info.sender != config.owner {
+    if info.sender != config.owner && info.sender != config.role_manager {
         return Err(cosmwasm_std::StdError::generic_err("Unauthorized"));
     }
     
@@ -1771,6 +1963,82 @@ pub fn unpause(
         .add_attribute("unpaused_by", info.sender.to_string()))
 }
 
+pub fn executeOnBehalf(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    let action = session_action(&msg)
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("Message cannot be sent by a session key"))?;
+    
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    if !is_session_authorized(deps.as_ref(), &owner_addr, &info.sender, action)? {
+        return Err(cosmwasm_std::StdError::generic_err("Session not authorized"));
+    }
+    
+    // A session key edits the owner's own posts only, whatever else the owner may touch
+    if let ExecuteMsg::UpdatePost { post_id, .. } | ExecuteMsg::DeletePost { post_id } = &msg {
+        if !is_post_creator(deps.as_ref(), *post_id, &owner_addr)? {
+            return Err(cosmwasm_std::StdError::generic_err("Not post creator"));
+        }
+    }
+    
+    // Run the inner message as the owner; funds still come from the session key
+    let session = info.sender;
+    let owner_info = MessageInfo {
+        sender: owner_addr,
+        funds: info.funds,
+    };
+    let response = execute(deps, env, owner_info, msg)?;
+    
+    Ok(response
+        .add_attribute("on_behalf_of", owner)
+        .add_attribute("session", session))
+}
+
+// Keep the local role cache in sync with role_manager
+pub fn roleChanged(
+    deps: DepsMut,
+    info: MessageInfo,
+    user: String,
+    role: String,
+    scope: Option<String>,
+    granted: bool,
+) -> StdResult<Response> {
+    // Only role_manager may push role updates
+    let config = get_config(deps.storage)?;
+    if info.sender != config.role_manager {
+        return Err(cosmwasm_std::StdError::generic_err("Unauthorized"));
+    }
+    
+    // The local cache only mirrors global grants; scoped ones are queried live
+    if scope.is_some() {
+        return Ok(Response::new()
+            .add_attribute("action", "role_changed")
+            .add_attribute("ignored", "scoped"));
+    }
+    if !CACHED_ROLES.contains(&role.as_str()) {
+        return Ok(Response::new()
+            .add_attribute("action", "role_changed")
+            .add_attribute("ignored", "uncached"));
+    }
+    
+    let user_addr = deps.api.addr_validate(&user)?;
+    if granted {
+        grant_role(deps.storage, user_addr, role.clone())?;
+    } else {
+        revoke_role(deps.storage, &user_addr, &role);
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "role_changed")
+        .add_attribute("user", user)
+        .add_attribute("role", role)
+        .add_attribute("granted", granted.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1793,4 +2061,124 @@ mod tests {
         assert_eq!("instantiate", res.attributes[0].value);
         assert_eq!("owner", res.attributes[1].value);
     }
+    
+    #[test]
+    fn test_matrix_actions_query_role_manager() {
+        let mut deps = cosmwasm_std::testing::mock_dependencies();
+        let env = cosmwasm_std::testing::mock_env();
+        let msg = InstantiateMsg {
+            role_manager: "role_manager".to_string(),
+            tribe_controller: "tribe_controller".to_string(),
+            collectible_controller: "collectible_controller".to_string(),
+            feed_manager: "feed_manager".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), cosmwasm_std::testing::mock_info("owner", &[]), msg).unwrap();
+        
+        // role_manager lets "admin" set cooldowns and "mod" moderate tribe 1 only
+        deps.querier.update_wasm(|query| {
+            let binary = match query {
+                WasmQuery::Smart { contract_addr, .. } if contract_addr == "tribe_controller" => {
+                    to_json_binary(&MemberStatusResponse { status: MemberStatus::ACTIVE })
+                },
+                WasmQuery::Smart { msg, .. } => {
+                    let result = match from_json(msg).unwrap() {
+                        RoleManagerQuery::CanPerform { user, action, scope } => {
+                            (user == "admin" && action == ACTION_SET_COOLDOWN)
+                                || (user == "mod" && action == ACTION_MODERATE && scope.as_deref() == Some("tribe:1"))
+                        },
+                        _ => false,
+                    };
+                    to_json_binary(&BoolResponse { result })
+                },
+                _ => unimplemented!(),
+            };
+            cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(binary.unwrap()))
+        });
+        
+        // The owner's local RATE_LIMIT_MANAGER_ROLE no longer bypasses the matrix
+        setPostTypeCooldown(deps.as_mut(), cosmwasm_std::testing::mock_info("owner", &[]), PostType::TEXT, 10).unwrap_err();
+        setPostTypeCooldown(deps.as_mut(), cosmwasm_std::testing::mock_info("admin", &[]), PostType::TEXT, 0).unwrap();
+        
+        // Moderators may delete other members' posts within their tribe
+        let create = |tribe_id: u64| ExecuteMsg::CreatePost {
+            tribe_id,
+            metadata: "{}".to_string(),
+            is_gated: false,
+            collectible_contract: None,
+            collectible_id: 0,
+        };
+        let alice_info = cosmwasm_std::testing::mock_info("alice", &[]);
+        execute(deps.as_mut(), env.clone(), alice_info.clone(), create(1)).unwrap();
+        execute(deps.as_mut(), env.clone(), alice_info, create(2)).unwrap();
+        let err = deletePost(deps.as_mut(), env.clone(), cosmwasm_std::testing::mock_info("bob", &[]), 0).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Not post creator"), err);
+        deletePost(deps.as_mut(), env.clone(), cosmwasm_std::testing::mock_info("mod", &[]), 1).unwrap_err();
+        deletePost(deps.as_mut(), env, cosmwasm_std::testing::mock_info("mod", &[]), 0).unwrap();
+        assert!(POSTS.load(deps.as_ref().storage, &u64_to_key(0)).unwrap().is_deleted);
+        
+        // A global admin revoke leaves the owner's local admin role alone
+        let role_manager_info = cosmwasm_std::testing::mock_info("role_manager", &[]);
+        let res = roleChanged(deps.as_mut(), role_manager_info, "owner".to_string(), DEFAULT_ADMIN_ROLE.to_string(), None, false).unwrap();
+        assert_eq!("uncached", res.attributes[1].value);
+        assert_eq!(Some(true), ROLES.may_load(deps.as_ref().storage, "owner:DEFAULT_ADMIN_ROLE").unwrap());
+    }
+    
+    #[test]
+    fn test_execute_on_behalf() {
+        let mut deps = cosmwasm_std::testing::mock_dependencies();
+        let env = cosmwasm_std::testing::mock_env();
+        let msg = InstantiateMsg {
+            role_manager: "role_manager".to_string(),
+            tribe_controller: "tribe_controller".to_string(),
+            collectible_controller: "collectible_controller".to_string(),
+            feed_manager: "feed_manager".to_string(),
+        };
+        instantiate(deps.as_mut(), env.clone(), cosmwasm_std::testing::mock_info("owner", &[]), msg).unwrap();
+        
+        // Everyone is an active member, and "phone" may post and edit for "alice"
+        deps.querier.update_wasm(|query| {
+            let binary = match query {
+                WasmQuery::Smart { contract_addr, .. } if contract_addr == "tribe_controller" => {
+                    to_json_binary(&MemberStatusResponse { status: MemberStatus::ACTIVE })
+                },
+                WasmQuery::Smart { msg, .. } => {
+                    let result = match from_json(msg).unwrap() {
+                        RoleManagerQuery::IsSessionAuthorized { owner, session, action } => {
+                            owner == "alice" && session == "phone" && (action == SESSION_ACTION_POST || action == SESSION_ACTION_EDIT)
+                        },
+                        _ => false,
+                    };
+                    to_json_binary(&BoolResponse { result })
+                },
+                _ => unimplemented!(),
+            };
+            cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(binary.unwrap()))
+        });
+        
+        let on_behalf = |msg: ExecuteMsg| ExecuteMsg::ExecuteOnBehalf { owner: "alice".to_string(), msg: Box::new(msg) };
+        let create = || ExecuteMsg::CreatePost {
+            tribe_id: 1,
+            metadata: "{}".to_string(),
+            is_gated: false,
+            collectible_contract: None,
+            collectible_id: 0,
+        };
+        
+        // The delegated post belongs to the owner
+        let phone_info = cosmwasm_std::testing::mock_info("phone", &[]);
+        execute(deps.as_mut(), env.clone(), phone_info.clone(), on_behalf(create())).unwrap();
+        assert_eq!(Addr::unchecked("alice"), POSTS.load(deps.as_ref().storage, &u64_to_key(0)).unwrap().creator);
+        execute(deps.as_mut(), env.clone(), cosmwasm_std::testing::mock_info("laptop", &[]), on_behalf(create())).unwrap_err();
+        
+        // Edits are limited to the owner's own posts
+        execute(deps.as_mut(), env.clone(), cosmwasm_std::testing::mock_info("bob", &[]), create()).unwrap();
+        let err = execute(deps.as_mut(), env.clone(), phone_info.clone(), on_behalf(ExecuteMsg::DeletePost { post_id: 1 })).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Not post creator"), err);
+        execute(deps.as_mut(), env.clone(), phone_info.clone(), on_behalf(ExecuteMsg::DeletePost { post_id: 0 })).unwrap();
+        
+        // Actions outside the session's scope are refused
+        let report = ExecuteMsg::ReportPost { post_id: 1, reason: "spam".to_string() };
+        let err = execute(deps.as_mut(), env, phone_info, on_behalf(report)).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Session not authorized"), err);
+    }
 } 
\ No newline at end of file
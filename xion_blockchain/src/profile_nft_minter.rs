@@ -77,7 +77,10 @@ pub enum ExecuteMsg {
     transfer_nft {
         recipient: String,
         token_id: u64,
-    }
+    },
+    SetPaused {
+        paused: bool,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -119,7 +122,8 @@ pub enum QueryMsg {
     },
     balance_of {
         owner: String,
-    }
+    },
+    IsPaused {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -127,6 +131,15 @@ pub struct BoolResponse {
     pub result: bool,
 }
 
+// Simple interface for querying role manager
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoleManagerQuery {
+    IsBlocked {
+        user: String,
+    },
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ProfileResponse {
     pub username: String,
@@ -192,6 +205,7 @@ pub fn instantiate(
     
     // Save config
     CONFIG.save(deps.storage, &config)?;
+    ADMIN.save(deps.storage, &info.sender)?;
     
     // Initialize the next token ID as 0
     NEXT_TOKEN_ID.save(deps.storage, &0u64)?;
@@ -207,6 +221,11 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
+    // Only pause control gets through while paused
+    if !matches!(msg, ExecuteMsg::SetPaused { .. }) && PAUSED.may_load(deps.storage)?.unwrap_or(false) {
+        return Err(cosmwasm_std::StdError::generic_err("Contract is paused"));
+    }
+    
     match msg {
         ExecuteMsg::CreateProfile { username, metadata_uri } => {
             createProfile(deps, env, info, username, metadata_uri)
@@ -233,6 +252,10 @@ pub fn execute(
             transferFrom(deps, env, info, recipient, token_id)
                 .map_err(|e| cosmwasm_std::StdError::generic_err(format!("{:?}", e)))
         },
+        ExecuteMsg::SetPaused { paused } => {
+            setPaused(deps, info, paused)
+                .map_err(|e| cosmwasm_std::StdError::generic_err(format!("{:?}", e)))
+        },
     }
 }
 
@@ -243,6 +266,11 @@ pub fn createProfile(
     username: String,
     metadata_uri: String,
 ) -> Result<Response, ContractError> {
+    // Blocked addresses cannot create profiles
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Blocked {});
+    }
+    
     // Validate username
     if !_validateUsername(&username) {
         return Err(ContractError::InvalidUsername {});
@@ -374,6 +402,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::supports_interface { interface_id } => to_json_binary(&supportsInterface(deps, interface_id)?),
         QueryMsg::owner_of { token_id } => to_json_binary(&ownerOf(deps, token_id)?),
         QueryMsg::balance_of { owner } => to_json_binary(&balanceOf(deps, owner)?),
+        QueryMsg::IsPaused {} => to_json_binary(&BoolResponse { result: PAUSED.may_load(deps.storage)?.unwrap_or(false) }),
     }
 }
 
@@ -383,6 +412,24 @@ pub fn usernameExists_query(deps: Deps, username: String) -> StdResult<BoolRespo
     Ok(BoolResponse { result })
 }
 
+// Check the platform-wide blocklist in role_manager; an unanswered query counts as blocked
+fn is_blocked(deps: Deps, addr: &Addr) -> StdResult<bool> {
+    let config = CONFIG.load(deps.storage)?;
+    let msg = to_json_binary(&RoleManagerQuery::IsBlocked {
+        user: addr.to_string(),
+    })?;
+    
+    let query = cosmwasm_std::WasmQuery::Smart {
+        contract_addr: config.role_manager.to_string(),
+        msg,
+    };
+    
+    match deps.querier.query::<BoolResponse>(&query.into()) {
+        Ok(response) => Ok(response.result),
+        Err(_) => Ok(true),
+    }
+}
+
 fn usernameExists(storage: &dyn cosmwasm_std::Storage, username: &str) -> StdResult<bool> {
     Ok(USERNAME_MAP.may_load(storage, username)?.is_some())
 }
@@ -753,9 +800,9 @@ pub fn setAdmin(deps: DepsMut, info: MessageInfo, new_admin: Addr) -> Result<Res
 
 // Set paused state
 pub fn setPaused(deps: DepsMut, info: MessageInfo, paused: bool) -> Result<Response, ContractError> {
-    // Check if sender is admin
-    let admin = ADMIN.load(deps.storage)?;
-    if info.sender != admin {
+    // role_manager relaying a platform-wide pause, or the admin
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.role_manager && ADMIN.may_load(deps.storage)?.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
     
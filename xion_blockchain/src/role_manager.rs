@@ -1,13 +1,17 @@
+// Handlers keep the camelCase names of the Solidity contracts they port
+#![allow(non_snake_case)]
+
 use cosmwasm_std::{
     to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Addr, Order,
-    entry_point,
+    entry_point, WasmMsg, Reply, SubMsg, SubMsgResult,
 };
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use crate::errors::ContractError;
+use crate::{post_minter, tribe_controller};
 use crate::testing::{mock_dependencies, mock_env, mock_info};
 
 // Constants for role definitions
@@ -17,27 +21,401 @@ pub const ARTIST_ROLE: &str = "ARTIST_ROLE";
 pub const BRAND_ROLE: &str = "BRAND_ROLE";
 pub const MODERATOR_ROLE: &str = "MODERATOR_ROLE";
 pub const FAN_ASSIGNER_ROLE: &str = "FAN_ASSIGNER_ROLE";
+pub const GUARDIAN_ROLE: &str = "GUARDIAN_ROLE";
 pub const DEFAULT_ADMIN_ROLE: &str = "DEFAULT_ADMIN_ROLE";
 
+// Roles that come with a soulbound badge token
+const BADGE_ROLES: [&str; 3] = [ARTIST_ROLE, ORGANIZER_ROLE, BRAND_ROLE];
+
+// Permission-matrix action allowed to block and unblock users
+pub const ACTION_BLOCK: &str = "role_manager.block";
+
+// Permission-matrix action allowed to pause the whole platform
+pub const ACTION_PAUSE_ALL: &str = "role_manager.pause_all";
+
+// Pagination defaults for list queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// Default cap on the number of items in a batch grant/revoke
+const DEFAULT_MAX_BATCH_SIZE: u32 = 100;
+
+// Default window for approving a proposal (3 days)
+const DEFAULT_VOTING_PERIOD: u64 = 259_200;
+
+// Reply id of RoleChanged hook notifications, so a failing subscriber cannot revert the change
+const HOOK_REPLY_ID: u64 = 1;
+const PLATFORM_PAUSE_REPLY_ID: u64 = 2;
+
 // Storage items using cw-storage-plus
-const ROLES: Map<(&str, &str), bool> = Map::new("roles");
+const CONFIG: Item<Config> = Item::new("config");
+const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+const NEXT_PROPOSAL_ID: Item<u64> = Item::new("next_proposal_id");
+const VOUCHER_SIGNERS: Map<&str, VoucherSigner> = Map::new("voucher_signers");
+// Claims per (signer, nonce), and which users already claimed a voucher
+const VOUCHER_CLAIM_COUNT: Map<(&str, u64), u32> = Map::new("voucher_claim_count");
+const VOUCHER_CLAIMED: Map<(&str, u64, &str), bool> = Map::new("voucher_claimed");
+const NEXT_AUDIT_ID: Item<u64> = Item::new("next_audit_id");
+// Action identifier (e.g. "post_minter.set_cooldown") -> roles allowed to perform it
+const PERMISSIONS: Map<&str, Vec<String>> = Map::new("permissions");
+// Role applications keyed by (role, applicant) so reviewers can list per role
+const APPLICATIONS: Map<(&str, &str), Application> = Map::new("applications");
+// Badges keyed by (owner, role); the token id is "{role}:{owner}"
+const BADGES: Map<(&str, &str), Badge> = Map::new("badges");
+const BADGE_URIS: Map<&str, String> = Map::new("badge_uris");
+// Session keys keyed by (owner, session address)
+const SESSION_KEYS: Map<(&str, &str), SessionKey> = Map::new("session_keys");
+const BLOCKLIST: Map<&str, BlockEntry> = Map::new("blocklist");
+const SCOPED_ROLES: Map<(&str, &str, &str), RoleGrant> = Map::new("scoped_roles");
+const HOOKS: Map<&str, bool> = Map::new("hooks");
+// Contracts paused and unpaused together by PauseAll/UnpauseAll
+const PLATFORM_CONTRACTS: Map<&str, PlatformContract> = Map::new("platform_contracts");
+const FAN_ASSIGNER_ALLOWANCES: Map<&str, FanAssignerAllowance> = Map::new("fan_assigner_allowances");
+const ROLES: Map<(&str, &str), RoleGrant> = Map::new("roles");
+// Same namespace as ROLES, read by `migrate` to find grants stored before expiries existed
+const STORED_ROLES: Map<(&str, &str), StoredRoleGrant> = Map::new("roles");
+
+// Secondary index of ROLES keyed by (role, user), plus member counts per role
+const ROLE_MEMBERS: Map<(&str, &str), bool> = Map::new("role_members");
+const ROLE_MEMBER_COUNT: Map<&str, u64> = Map::new("role_member_count");
+// Grants that expire, keyed by (role, expires_at, user) so counts can leave out lapsed ones
+const ROLE_EXPIRIES: Map<(&str, u64, &str), bool> = Map::new("role_expiries");
+
+// Admin role of each role; roles without an entry are administered by DEFAULT_ADMIN_ROLE
+const ROLE_ADMIN: Map<&str, String> = Map::new("role_admin");
+
+// Catalog of roles that may be granted
+const ROLE_REGISTRY: Map<&str, RoleInfo> = Map::new("role_registry");
+
+// Admin handover waiting to be accepted by the proposed address
+const PENDING_ADMIN: Item<PendingAdmin> = Item::new("pending_admin");
+
+// Pause scopes that can be frozen independently of the global pause
+// Audit log role recorded for blocklist changes, which are not tied to a role
+pub const AUDIT_ROLE_BLOCKLIST: &str = "BLOCKLIST";
+
+pub const PAUSE_SCOPE_GRANTS: &str = "grants";
+pub const PAUSE_SCOPE_FAN_ASSIGNMENT: &str = "fan_assignment";
+const PAUSE_SCOPES: [&str; 2] = [PAUSE_SCOPE_GRANTS, PAUSE_SCOPE_FAN_ASSIGNMENT];
 
 // Add storage for paused state
 pub const PAUSED: Item<bool> = Item::new("paused");
+const PAUSED_SCOPES: Map<&str, bool> = Map::new("paused_scopes");
+
+// A role held by an address, keyed by (user, role) in ROLES
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleGrant {
+    pub granted_at: u64,
+    pub granted_by: Addr,
+    pub expires_at: Option<u64>,
+}
+
+impl RoleGrant {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+// A ROLES value as found on chain: the original plain flag or a RoleGrant
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+enum StoredRoleGrant {
+    Legacy(bool),
+    Grant(RoleGrant),
+}
+
+// Registry entry describing a known role
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleInfo {
+    pub description: String,
+    pub deprecated: bool,
+    pub registered_at: u64,
+}
+
+// Two-step DEFAULT_ADMIN_ROLE handover from `proposer` to `new_admin`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdmin {
+    pub proposer: Addr,
+    pub new_admin: Addr,
+    pub proposed_at: u64,
+}
+
+// Bounded allowance for a fan assigner; `max_assignments: None` means unlimited
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FanAssignerAllowance {
+    pub max_assignments: Option<u64>,
+    pub assigned_count: u64,
+    pub expires_at: Option<u64>,
+}
+
+impl FanAssignerAllowance {
+    pub fn remaining(&self) -> Option<u64> {
+        self.max_assignments.map(|max| max.saturating_sub(self.assigned_count))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub max_batch_size: u32,
+    // Grants/revocations of these roles need multisig approval when the threshold is non-zero
+    pub sensitive_roles: Vec<String>,
+    pub approval_threshold: u32,
+    pub voting_period: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyType {
+    Secp256k1,
+    Ed25519,
+}
+
+// Off-chain key allowed to sign role vouchers for `roles`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherSigner {
+    pub pubkey: Binary,
+    pub key_type: KeyType,
+    pub roles: Vec<String>,
+    pub registered_by: Addr,
+}
+
+// Non-transferable token minted alongside a badge role
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Badge {
+    pub role: String,
+    pub issued_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Application {
+    pub applicant: Addr,
+    pub role: String,
+    pub evidence_uri: String,
+    pub status: ApplicationStatus,
+    pub submitted_at: u64,
+    pub reviewed_by: Option<Addr>,
+    pub reviewed_at: Option<u64>,
+    pub reason: Option<String>,
+}
+
+// Platform-wide ban; `until: None` blocks indefinitely
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlockEntry {
+    pub reason: String,
+    pub until: Option<u64>,
+    pub blocked_by: Addr,
+    pub blocked_at: u64,
+}
+
+impl BlockEntry {
+    pub fn is_active(&self, now: u64) -> bool {
+        self.until.is_none_or(|until| now < until)
+    }
+}
+
+// Delegated key allowed to perform `actions` on behalf of its owner until `expires_at`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SessionKey {
+    pub session: Addr,
+    pub actions: Vec<String>,
+    pub expires_at: u64,
+    pub created_at: u64,
+}
+
+// Fields covered by a voucher signature
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleVoucher {
+    pub role: String,
+    pub expires_at: u64,
+    pub nonce: u64,
+    pub max_claims: u32,
+}
+
+// Append-only record of a single role change
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub action: String,
+    pub actor: Addr,
+    pub target: Addr,
+    pub role: String,
+    #[serde(default)]
+    pub scope: Option<String>,
+    pub block_height: u64,
+    pub timestamp: u64,
+}
+
+pub struct AuditIndexes<'a> {
+    pub target: MultiIndex<'a, String, AuditEntry, u64>,
+    pub role: MultiIndex<'a, String, AuditEntry, u64>,
+    pub target_role: MultiIndex<'a, (String, String), AuditEntry, u64>,
+}
+
+impl IndexList<AuditEntry> for AuditIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AuditEntry>> + '_> {
+        let v: Vec<&dyn Index<AuditEntry>> = vec![&self.target, &self.role, &self.target_role];
+        Box::new(v.into_iter())
+    }
+}
+
+fn audit_log<'a>() -> IndexedMap<'a, u64, AuditEntry, AuditIndexes<'a>> {
+    let indexes = AuditIndexes {
+        target: MultiIndex::new(|_pk, entry| entry.target.to_string(), "audit_log", "audit_log__target"),
+        role: MultiIndex::new(|_pk, entry| entry.role.clone(), "audit_log", "audit_log__role"),
+        target_role: MultiIndex::new(
+            |_pk, entry| (entry.target.to_string(), entry.role.clone()),
+            "audit_log",
+            "audit_log__target_role",
+        ),
+    };
+    IndexedMap::new("audit_log", indexes)
+}
+
+// Change that only takes effect once enough admins approve it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalAction {
+    GrantRole { user: String, role: String, expires_at: Option<u64> },
+    RevokeRole { user: String, role: String },
+    ExtendRole { user: String, role: String, expires_at: Option<u64> },
+    GrantScopedRole { user: String, role: String, scope: String, expires_at: Option<u64> },
+    RevokeScopedRole { user: String, role: String, scope: String },
+    UpdateSecurity { sensitive_roles: Vec<String>, approval_threshold: u32, voting_period: u64 },
+    ProposeAdmin { new_admin: String },
+    SetRoleAdmin { role: String, admin_role: String },
+    RegisterRole { role: String, description: String, admin_role: String },
+    SetPermission { action: String, roles: Vec<String> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Open,
+    Executed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub id: u64,
+    pub action: ProposalAction,
+    pub proposer: Addr,
+    pub approvals: Vec<Addr>,
+    pub status: ProposalStatus,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleAssignment {
+    pub user: String,
+    pub role: String,
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {}
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+// Message sent to every registered hook contract when a role is granted or removed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RoleHookMsg {
+    // `scope` is set for scoped grants, e.g. "tribe:5"
+    RoleChanged { user: String, role: String, scope: Option<String>, granted: bool },
+}
+
+// Platform contracts differ in how they are paused
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformContractKind {
+    // Pause {} / Unpause {}
+    PostMinter,
+    // SetPaused { paused }
+    ProfileNftMinter,
+    // Pause {} / Unpause {}
+    TribeController,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlatformContract {
+    pub name: String,
+    pub kind: PlatformContractKind,
+}
+
+// Pause messages understood by the platform contracts
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformPauseMsg {
+    Pause {},
+    Unpause {},
+    SetPaused { paused: bool },
+}
+
+// Pause-state query implemented by every platform contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlatformQuery {
+    IsPaused {},
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    GrantRole { user: String, role: String },
+    GrantRole { user: String, role: String, expires_at: Option<u64> },
+    ExtendRole { user: String, role: String, expires_at: Option<u64> },
     RevokeRole { user: String, role: String },
     RenounceRole { role: String },
-    AuthorizeFanAssigner { assigner: String },
+    AuthorizeFanAssigner { assigner: String, max_assignments: Option<u64>, expires_at: Option<u64> },
+    RevokeFanAssigner { assigner: String },
     AssignFanRole { user: String },
-    Pause {},
-    Unpause {},
+    SetRoleAdmin { role: String, admin_role: String },
+    RegisterRole { role: String, description: String, admin_role: String },
+    DeprecateRole { role: String },
+    ProposeAdmin { new_admin: String },
+    AcceptAdmin {},
+    BatchGrantRoles { grants: Vec<RoleAssignment> },
+    BatchRevokeRoles { revocations: Vec<RoleAssignment> },
+    UpdateConfig { max_batch_size: Option<u32> },
+    UpdateSecurity { sensitive_roles: Vec<String>, approval_threshold: u32, voting_period: u64 },
+    ApproveProposal { proposal_id: u64 },
+    ExecuteProposal { proposal_id: u64 },
+    CancelProposal { proposal_id: u64 },
+    RegisterVoucherSigner { name: String, pubkey: Binary, key_type: KeyType, roles: Vec<String> },
+    RemoveVoucherSigner { name: String },
+    // `expires_at` is the voucher's claim deadline, not the role's expiry; `signer` names the registered key
+    ClaimRole { signer: String, role: String, expires_at: u64, nonce: u64, max_claims: u32, signature: Binary },
+    GrantScopedRole { user: String, role: String, scope: String, expires_at: Option<u64> },
+    RevokeScopedRole { user: String, role: String, scope: String },
+    SetBadgeUri { role: String, token_uri: Option<String> },
+    ApplyForRole { role: String, evidence_uri: String },
+    ApproveApplication { applicant: String, role: String },
+    RejectApplication { applicant: String, role: String, reason: String },
+    RegisterSessionKey { session: String, actions: Vec<String>, expires_at: u64 },
+    RevokeSessionKey { session: String },
+    Block { user: String, reason: String, until: Option<u64> },
+    Unblock { user: String },
+    SetPermission { action: String, roles: Vec<String> },
+    RemovePermission { action: String },
+    AddHook { contract: String },
+    RegisterPlatformContract { contract: String, name: String, kind: PlatformContractKind },
+    RemovePlatformContract { contract: String },
+    PauseAll {},
+    UnpauseAll {},
+    RemoveHook { contract: String },
+    // Anyone may clear a lapsed grant so hook subscribers are told it is gone
+    ClearExpiredRole { user: String, role: String, scope: Option<String> },
+    Pause { scope: Option<String> },
+    Unpause { scope: Option<String> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -45,8 +423,38 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     HasRole { user: String, role: String },
     HasAnyRole { user: String, roles: Vec<String> },
+    HasScopedRole { user: String, role: String, scope: String },
+    CanPerform { user: String, action: String, scope: Option<String> },
+    IsBlocked { user: String },
+    IsSessionAuthorized { owner: String, session: String, action: String },
+    ListSessionKeys { owner: String, start_after: Option<String>, limit: Option<u32> },
+    // cw721-compatible badge queries
+    NftInfo { token_id: String },
+    OwnerOf { token_id: String, include_expired: Option<bool> },
+    Tokens { owner: String, start_after: Option<String>, limit: Option<u32> },
+    GetApplication { applicant: String, role: String },
+    ListApplications { role: String, status: Option<ApplicationStatus>, start_after: Option<String>, limit: Option<u32> },
+    GetBlockEntry { user: String },
+    ListPermissions { start_after: Option<String>, limit: Option<u32> },
     HasAllRoles { user: String, roles: Vec<String> },
     GetUserRoles { user: String },
+    GetRoleAdmin { role: String },
+    GetRoleGrant { user: String, role: String },
+    ListRoleMembers { role: String, start_after: Option<String>, limit: Option<u32> },
+    CountRoleMembers { role: String },
+    ListRoles {},
+    ListRegisteredRoles { start_after: Option<String>, limit: Option<u32> },
+    IsPaused { scope: Option<String> },
+    GetPendingAdmin {},
+    GetConfig {},
+    GetFanAssignerAllowance { assigner: String },
+    ListHooks {},
+    PlatformStatus {},
+    ListVoucherSigners {},
+    GetVoucherClaims { signer: String, nonce: u64 },
+    GetProposal { proposal_id: u64 },
+    ListProposals { start_after: Option<u64>, limit: Option<u32> },
+    AuditLog { user: Option<String>, role: Option<String>, start_after: Option<u64>, limit: Option<u32> },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -59,78 +467,465 @@ pub struct BoolResponse {
     pub result: bool,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMembersResponse {
+    pub members: Vec<String>,
+    // Set when the page scanned `limit` grants; pass it as the next `start_after`
+    pub next_start_after: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleMemberCountResponse {
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleGrantResponse {
+    pub grant: Option<RoleGrant>,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RegisteredRole {
+    pub role: String,
+    pub description: String,
+    pub admin_role: String,
+    pub deprecated: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RegisteredRolesResponse {
+    pub roles: Vec<RegisteredRole>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FanAssignerAllowanceResponse {
+    pub allowance: Option<FanAssignerAllowance>,
+    pub remaining: Option<u64>,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<Proposal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherSignerInfo {
+    pub name: String,
+    pub signer: VoucherSigner,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherSignersResponse {
+    pub signers: Vec<VoucherSignerInfo>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoucherClaimsResponse {
+    pub claims: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NftInfoResponse {
+    pub token_uri: Option<String>,
+    pub extension: Badge,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnerOfResponse {
+    pub owner: String,
+    // Always empty: badges cannot be transferred or approved
+    pub approvals: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApplicationResponse {
+    pub application: Option<Application>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ApplicationsResponse {
+    pub applications: Vec<Application>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SessionKeysResponse {
+    pub session_keys: Vec<SessionKey>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BlockEntryResponse {
+    pub entry: Option<BlockEntry>,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Permission {
+    pub action: String,
+    pub roles: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PermissionsResponse {
+    pub permissions: Vec<Permission>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlatformContractStatus {
+    pub contract: String,
+    pub name: String,
+    pub kind: PlatformContractKind,
+    // None when the contract could not be queried
+    pub paused: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PlatformStatusResponse {
+    pub role_manager_paused: bool,
+    pub contracts: Vec<PlatformContractStatus>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HooksResponse {
+    pub hooks: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingAdminResponse {
+    pub pending: Option<PendingAdmin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoleAdminResponse {
+    pub role: String,
+    pub admin_role: String,
+}
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     _msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    CONFIG.save(deps.storage, &default_config())?;
+
     // Grant the contract deployer the default admin role
-    ROLES.save(deps.storage, (info.sender.as_str(), DEFAULT_ADMIN_ROLE), &true)?;
+    let grant = RoleGrant {
+        granted_at: env.block.time.seconds(),
+        granted_by: info.sender.clone(),
+        expires_at: None,
+    };
+    save_role(deps.storage, &info.sender, DEFAULT_ADMIN_ROLE, &grant)?;
+
+    seed_built_in_roles(deps.storage, &env)?;
+    seed_default_permissions(deps.storage)?;
 
     Ok(Response::new()
         .add_attribute("action", "instantiate")
         .add_attribute("admin", info.sender))
 }
 
+// Brings storage written by earlier versions up to the current layout; safe to run more than once
+#[entry_point]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Plain `true` flags become permanent grants; the original grant time and granter were never stored
+    let legacy = STORED_ROLES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok(((user, role), StoredRoleGrant::Legacy(held))) => Some(Ok((user, role, held))),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut migrated_roles = 0u64;
+    for (user, role, held) in legacy {
+        // Drop the old value first so save_role counts the member and builds the index
+        STORED_ROLES.remove(deps.storage, (&user, &role));
+        if held {
+            let grant = RoleGrant {
+                granted_at: env.block.time.seconds(),
+                granted_by: env.contract.address.clone(),
+                expires_at: None,
+            };
+            save_role(deps.storage, &Addr::unchecked(user), &role, &grant)?;
+            migrated_roles += 1;
+        }
+    }
+
+    // Earlier versions had no config at all
+    if CONFIG.may_load(deps.storage)?.is_none() {
+        CONFIG.save(deps.storage, &default_config())?;
+    }
+    seed_built_in_roles(deps.storage, &env)?;
+    seed_default_permissions(deps.storage)?;
+
+    // Custom roles could be granted freely before the registry existed
+    let held_roles = ROLE_MEMBER_COUNT
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut registered_roles = 0u64;
+    for role in held_roles {
+        if !ROLE_REGISTRY.has(deps.storage, &role) {
+            let role_info = RoleInfo {
+                description: "Registered by migration".to_string(),
+                deprecated: false,
+                registered_at: env.block.time.seconds(),
+            };
+            ROLE_REGISTRY.save(deps.storage, &role, &role_info)?;
+            registered_roles += 1;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("migrated_roles", migrated_roles.to_string())
+        .add_attribute("registered_roles", registered_roles.to_string()))
+}
+
+fn default_config() -> Config {
+    Config {
+        max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        sensitive_roles: vec![DEFAULT_ADMIN_ROLE.to_string(), MODERATOR_ROLE.to_string()],
+        approval_threshold: 0,
+        voting_period: DEFAULT_VOTING_PERIOD,
+    }
+}
+
+// Default permission matrix for the platform contracts, keeping any actions already configured.
+// post_minter also honours its local RATE_LIMIT_MANAGER_ROLE and PROJECT_CREATOR_ROLE for its actions.
+fn seed_default_permissions(storage: &mut dyn cosmwasm_std::Storage) -> StdResult<()> {
+    let default_permissions = [
+        (post_minter::ACTION_SET_COOLDOWN, vec![DEFAULT_ADMIN_ROLE]),
+        (post_minter::ACTION_BYPASS_COOLDOWN, vec![DEFAULT_ADMIN_ROLE]),
+        (post_minter::ACTION_MODERATE, vec![MODERATOR_ROLE]),
+        (post_minter::ACTION_PROJECT_UPDATE, vec![ARTIST_ROLE, ORGANIZER_ROLE, BRAND_ROLE]),
+        (tribe_controller::ACTION_MODERATE, vec![MODERATOR_ROLE]),
+        (tribe_controller::ACTION_SET_PROTOCOL_FEE, vec![DEFAULT_ADMIN_ROLE]),
+        (ACTION_BLOCK, vec![DEFAULT_ADMIN_ROLE, MODERATOR_ROLE]),
+        (ACTION_PAUSE_ALL, vec![DEFAULT_ADMIN_ROLE, GUARDIAN_ROLE]),
+    ];
+    for (action, roles) in default_permissions {
+        if !PERMISSIONS.has(storage, action) {
+            let roles = roles.into_iter().map(str::to_string).collect::<Vec<_>>();
+            PERMISSIONS.save(storage, action, &roles)?;
+        }
+    }
+    Ok(())
+}
+
+// Registers the built-in roles and the FAN_ROLE admin, keeping any existing entries
+fn seed_built_in_roles(storage: &mut dyn cosmwasm_std::Storage, env: &Env) -> StdResult<()> {
+    // Fan assigners administer FAN_ROLE
+    if !ROLE_ADMIN.has(storage, FAN_ROLE) {
+        ROLE_ADMIN.save(storage, FAN_ROLE, &FAN_ASSIGNER_ROLE.to_string())?;
+    }
+
+    let built_in_roles = [
+        (DEFAULT_ADMIN_ROLE, "Manages the role catalog and administers roles by default"),
+        (FAN_ROLE, "Fan of the platform"),
+        (ORGANIZER_ROLE, "Event organizer"),
+        (ARTIST_ROLE, "Verified artist"),
+        (BRAND_ROLE, "Brand partner"),
+        (MODERATOR_ROLE, "Platform moderator"),
+        (FAN_ASSIGNER_ROLE, "Partner app allowed to assign FAN_ROLE"),
+        (GUARDIAN_ROLE, "Emergency responder allowed to pause the platform"),
+    ];
+    for (role, description) in built_in_roles {
+        if ROLE_REGISTRY.has(storage, role) {
+            continue;
+        }
+        let role_info = RoleInfo {
+            description: description.to_string(),
+            deprecated: false,
+            registered_at: env.block.time.seconds(),
+        };
+        ROLE_REGISTRY.save(storage, role, &role_info)?;
+    }
+    Ok(())
+}
+
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    ensure_not_paused(deps.storage, &msg)?;
+
     match msg {
-        ExecuteMsg::GrantRole { user, role } => grantRole(deps, info, user, role),
-        ExecuteMsg::RevokeRole { user, role } => revokeRole(deps, info, user, role),
-        ExecuteMsg::RenounceRole { role } => renounceRole(deps, info, role),
-        ExecuteMsg::AuthorizeFanAssigner { assigner } => authorizeFanAssigner(deps, info, assigner),
-        ExecuteMsg::AssignFanRole { user } => assignFanRole(deps, info, user),
-        ExecuteMsg::Pause {} => pause(deps, info),
-        ExecuteMsg::Unpause {} => unpause(deps, info),
+        ExecuteMsg::GrantRole { user, role, expires_at } => grantRole(deps, env, info, user, role, expires_at),
+        ExecuteMsg::ExtendRole { user, role, expires_at } => extendRole(deps, env, info, user, role, expires_at),
+        ExecuteMsg::RevokeRole { user, role } => revokeRole(deps, env, info, user, role),
+        ExecuteMsg::RenounceRole { role } => renounceRole(deps, env, info, role),
+        ExecuteMsg::AuthorizeFanAssigner { assigner, max_assignments, expires_at } => authorizeFanAssigner(deps, env, info, assigner, max_assignments, expires_at),
+        ExecuteMsg::RevokeFanAssigner { assigner } => revokeFanAssigner(deps, env, info, assigner),
+        ExecuteMsg::AssignFanRole { user } => assignFanRole(deps, env, info, user),
+        ExecuteMsg::SetRoleAdmin { role, admin_role } => setRoleAdmin(deps, env, info, role, admin_role),
+        ExecuteMsg::RegisterRole { role, description, admin_role } => registerRole(deps, env, info, role, description, admin_role),
+        ExecuteMsg::DeprecateRole { role } => deprecateRole(deps, env, info, role),
+        ExecuteMsg::ProposeAdmin { new_admin } => proposeAdmin(deps, env, info, new_admin),
+        ExecuteMsg::AcceptAdmin {} => acceptAdmin(deps, env, info),
+        ExecuteMsg::BatchGrantRoles { grants } => batchGrantRoles(deps, env, info, grants),
+        ExecuteMsg::BatchRevokeRoles { revocations } => batchRevokeRoles(deps, env, info, revocations),
+        ExecuteMsg::UpdateConfig { max_batch_size } => updateConfig(deps, env, info, max_batch_size),
+        ExecuteMsg::UpdateSecurity { sensitive_roles, approval_threshold, voting_period } => updateSecurity(deps, env, info, sensitive_roles, approval_threshold, voting_period),
+        ExecuteMsg::ApproveProposal { proposal_id } => approveProposal(deps, env, info, proposal_id),
+        ExecuteMsg::ExecuteProposal { proposal_id } => executeProposal(deps, env, info, proposal_id),
+        ExecuteMsg::CancelProposal { proposal_id } => cancelProposal(deps, env, info, proposal_id),
+        ExecuteMsg::RegisterVoucherSigner { name, pubkey, key_type, roles } => registerVoucherSigner(deps, env, info, name, pubkey, key_type, roles),
+        ExecuteMsg::RemoveVoucherSigner { name } => removeVoucherSigner(deps, env, info, name),
+        ExecuteMsg::ClaimRole { signer, role, expires_at, nonce, max_claims, signature } => {
+            claimRole(deps, env, info, signer, RoleVoucher { role, expires_at, nonce, max_claims }, signature)
+        }
+        ExecuteMsg::GrantScopedRole { user, role, scope, expires_at } => grantScopedRole(deps, env, info, user, role, scope, expires_at),
+        ExecuteMsg::RevokeScopedRole { user, role, scope } => revokeScopedRole(deps, env, info, user, role, scope),
+        ExecuteMsg::RegisterSessionKey { session, actions, expires_at } => registerSessionKey(deps, env, info, session, actions, expires_at),
+        ExecuteMsg::RevokeSessionKey { session } => revokeSessionKey(deps, info, session),
+        ExecuteMsg::SetBadgeUri { role, token_uri } => setBadgeUri(deps, env, info, role, token_uri),
+        ExecuteMsg::ApplyForRole { role, evidence_uri } => applyForRole(deps, env, info, role, evidence_uri),
+        ExecuteMsg::ApproveApplication { applicant, role } => approveApplication(deps, env, info, applicant, role),
+        ExecuteMsg::RejectApplication { applicant, role, reason } => rejectApplication(deps, env, info, applicant, role, reason),
+        ExecuteMsg::Block { user, reason, until } => block(deps, env, info, user, reason, until),
+        ExecuteMsg::Unblock { user } => unblock(deps, env, info, user),
+        ExecuteMsg::SetPermission { action, roles } => setPermission(deps, env, info, action, roles),
+        ExecuteMsg::RemovePermission { action } => removePermission(deps, env, info, action),
+        ExecuteMsg::AddHook { contract } => addHook(deps, env, info, contract),
+        ExecuteMsg::RegisterPlatformContract { contract, name, kind } => registerPlatformContract(deps, env, info, contract, name, kind),
+        ExecuteMsg::RemovePlatformContract { contract } => removePlatformContract(deps, env, info, contract),
+        ExecuteMsg::PauseAll {} => pauseAll(deps, env, info),
+        ExecuteMsg::UnpauseAll {} => unpauseAll(deps, env, info),
+        ExecuteMsg::RemoveHook { contract } => removeHook(deps, env, info, contract),
+        ExecuteMsg::ClearExpiredRole { user, role, scope } => clearExpiredRole(deps, env, info, user, role, scope),
+        ExecuteMsg::Pause { scope } => pause(deps, env, info, scope),
+        ExecuteMsg::Unpause { scope } => unpause(deps, env, info, scope),
+    }
+}
+
+// A hook that fails to process RoleChanged is only reported; the role change itself stands.
+// Likewise a platform contract that rejects PauseAll/UnpauseAll does not hold up the others.
+#[entry_point]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match (msg.id, msg.result) {
+        (HOOK_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "role_hook_failed")
+            .add_attribute("error", err)),
+        (PLATFORM_PAUSE_REPLY_ID, SubMsgResult::Err(err)) => Ok(Response::new()
+            .add_attribute("action", "platform_pause_failed")
+            .add_attribute("error", err)),
+        (id, _) => Err(ContractError::CustomError { message: format!("Unknown reply id: {}", id) }),
     }
 }
 
 pub fn grantRole(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     user: String,
     role: String,
+    expires_at: Option<u64>,
 ) -> Result<Response, ContractError> {
-    // Check if sender has admin role
-    if !has_role(deps.storage, &info.sender, DEFAULT_ADMIN_ROLE)? {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Check if sender holds the admin role of the role being granted
+    only_role_admin(deps.storage, &env, &info.sender, &role)?;
+    ensure_grantable(deps.storage, &role)?;
+    ensure_not_fan_assigner(&role)?;
 
     let user_addr = deps.api.addr_validate(&user)?;
+    validate_expiry(&env, &role, expires_at)?;
+
+    if requires_approval(deps.storage, &role)? {
+        let action = ProposalAction::GrantRole { user: user_addr.to_string(), role, expires_at };
+        return create_proposal(deps, env, info, action);
+    }
     
-    // Grant role to user
-    ROLES.save(deps.storage, (user_addr.as_str(), &role), &true)?;
+    // Grant role to user; fan assigners granting FAN_ROLE directly still use up their allowance
+    consume_fan_allowance(deps.storage, &env, &info.sender, &role)?;
+    let grant = RoleGrant {
+        granted_at: env.block.time.seconds(),
+        granted_by: info.sender.clone(),
+        expires_at,
+    };
+    save_role(deps.storage, &user_addr, &role, &grant)?;
+    record_audit(deps.storage, &env, "grant_role", &info.sender, &user_addr, &role, None)?;
 
     Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &user_addr, &role, None, true)?)
         .add_attribute("action", "grant_role")
         .add_attribute("user", user)
-        .add_attribute("role", role))
+        .add_attribute("role", role)
+        .add_attribute("expires_at", format_expiry(expires_at)))
 }
 
-pub fn revokeRole(
+pub fn extendRole(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     user: String,
     role: String,
+    expires_at: Option<u64>,
 ) -> Result<Response, ContractError> {
-    // Check if sender has admin role
-    if !has_role(deps.storage, &info.sender, DEFAULT_ADMIN_ROLE)? {
-        return Err(ContractError::Unauthorized {});
+    // Check if sender holds the admin role of the role being extended
+    only_role_admin(deps.storage, &env, &info.sender, &role)?;
+    ensure_grantable(deps.storage, &role)?;
+
+    let user_addr = deps.api.addr_validate(&user)?;
+    validate_expiry(&env, &role, expires_at)?;
+    
+    if !ROLES.has(deps.storage, (user_addr.as_str(), &role)) {
+        return Err(ContractError::CustomError { message: "Role not granted".to_string() });
+    }
+
+    // Extending a sensitive grant is as powerful as granting it
+    let action = ProposalAction::ExtendRole { user: user_addr.to_string(), role: role.clone(), expires_at };
+    if requires_approval(deps.storage, &role)? {
+        return create_proposal(deps, env, info, action);
     }
+    let msgs = apply_proposal_action(deps, &env, &info.sender, &action)?;
+
+    Ok(Response::new()
+        .add_submessages(msgs)
+        .add_attribute("action", "extend_role")
+        .add_attribute("user", user)
+        .add_attribute("role", role)
+        .add_attribute("expires_at", format_expiry(expires_at)))
+}
+
+pub fn revokeRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    role: String,
+) -> Result<Response, ContractError> {
+    // Check if sender holds the admin role of the role being revoked
+    only_role_admin(deps.storage, &env, &info.sender, &role)?;
 
     let user_addr = deps.api.addr_validate(&user)?;
+
+    if requires_approval(deps.storage, &role)? {
+        let action = ProposalAction::RevokeRole { user: user_addr.to_string(), role };
+        return create_proposal(deps, env, info, action);
+    }
     
     // Revoke role from user
-    ROLES.remove(deps.storage, (user_addr.as_str(), &role));
+    remove_role(deps.storage, &user_addr, &role)?;
+    record_audit(deps.storage, &env, "revoke_role", &info.sender, &user_addr, &role, None)?;
 
     Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &user_addr, &role, None, false)?)
         .add_attribute("action", "revoke_role")
         .add_attribute("user", user)
         .add_attribute("role", role))
@@ -138,14 +933,19 @@ pub fn revokeRole(
 
 pub fn renounceRole(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     role: String,
 ) -> Result<Response, ContractError> {
     // User can only renounce their own roles
-    // Remove the role from the sender
-    ROLES.remove(deps.storage, (info.sender.as_str(), &role));
+    if !ROLES.has(deps.storage, (info.sender.as_str(), &role)) {
+        return Err(ContractError::CustomError { message: "Role not held".to_string() });
+    }
+    remove_role(deps.storage, &info.sender, &role)?;
+    record_audit(deps.storage, &env, "renounce_role", &info.sender, &info.sender, &role, None)?;
 
     Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &info.sender, &role, None, false)?)
         .add_attribute("action", "renounce_role")
         .add_attribute("user", info.sender)
         .add_attribute("role", role))
@@ -153,198 +953,3114 @@ pub fn renounceRole(
 
 pub fn authorizeFanAssigner(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     assigner: String,
+    max_assignments: Option<u64>,
+    expires_at: Option<u64>,
 ) -> Result<Response, ContractError> {
-    // Check if sender has admin role
-    if !has_role(deps.storage, &info.sender, DEFAULT_ADMIN_ROLE)? {
-        return Err(ContractError::Unauthorized {});
-    }
+    // Check if sender holds the admin role of FAN_ASSIGNER_ROLE
+    only_role_admin(deps.storage, &env, &info.sender, FAN_ASSIGNER_ROLE)?;
+    ensure_grantable(deps.storage, FAN_ASSIGNER_ROLE)?;
 
     let assigner_addr = deps.api.addr_validate(&assigner)?;
+    validate_expiry(&env, FAN_ASSIGNER_ROLE, expires_at)?;
     
     // Grant FAN_ASSIGNER_ROLE to assigner
-    ROLES.save(deps.storage, (assigner_addr.as_str(), FAN_ASSIGNER_ROLE), &true)?;
+    let grant = RoleGrant {
+        granted_at: env.block.time.seconds(),
+        granted_by: info.sender.clone(),
+        expires_at,
+    };
+    save_role(deps.storage, &assigner_addr, FAN_ASSIGNER_ROLE, &grant)?;
+
+    // Re-authorizing resets the counter
+    let allowance = FanAssignerAllowance {
+        max_assignments,
+        assigned_count: 0,
+        expires_at,
+    };
+    FAN_ASSIGNER_ALLOWANCES.save(deps.storage, assigner_addr.as_str(), &allowance)?;
+    record_audit(deps.storage, &env, "authorize_fan_assigner", &info.sender, &assigner_addr, FAN_ASSIGNER_ROLE, None)?;
 
     Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &assigner_addr, FAN_ASSIGNER_ROLE, None, true)?)
         .add_attribute("action", "authorize_fan_assigner")
+        .add_attribute("assigner", assigner)
+        .add_attribute("max_assignments", max_assignments.map_or("unlimited".to_string(), |max| max.to_string()))
+        .add_attribute("expires_at", format_expiry(expires_at)))
+}
+
+pub fn revokeFanAssigner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assigner: String,
+) -> Result<Response, ContractError> {
+    // Check if sender holds the admin role of FAN_ASSIGNER_ROLE
+    only_role_admin(deps.storage, &env, &info.sender, FAN_ASSIGNER_ROLE)?;
+
+    let assigner_addr = deps.api.addr_validate(&assigner)?;
+    remove_role(deps.storage, &assigner_addr, FAN_ASSIGNER_ROLE)?;
+    record_audit(deps.storage, &env, "revoke_fan_assigner", &info.sender, &assigner_addr, FAN_ASSIGNER_ROLE, None)?;
+
+    Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &assigner_addr, FAN_ASSIGNER_ROLE, None, false)?)
+        .add_attribute("action", "revoke_fan_assigner")
         .add_attribute("assigner", assigner))
 }
 
 pub fn assignFanRole(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     user: String,
 ) -> Result<Response, ContractError> {
-    // Check if sender has admin role or FAN_ASSIGNER_ROLE
-    if !has_role(deps.storage, &info.sender, DEFAULT_ADMIN_ROLE)? && 
-       !has_role(deps.storage, &info.sender, FAN_ASSIGNER_ROLE)? {
-        return Err(ContractError::Unauthorized {});
+    // The default admin can always assign fans; anyone else needs the admin role of FAN_ROLE
+    // (FAN_ASSIGNER_ROLE by default)
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        only_role_admin(deps.storage, &env, &info.sender, FAN_ROLE)?;
     }
+    ensure_grantable(deps.storage, FAN_ROLE)?;
 
     let user_addr = deps.api.addr_validate(&user)?;
+
+    // Consume one assignment from the assigner's allowance, if it has one
+    consume_fan_allowance(deps.storage, &env, &info.sender, FAN_ROLE)?;
     
-    // Grant FAN_ROLE to user
-    ROLES.save(deps.storage, (user_addr.as_str(), FAN_ROLE), &true)?;
+    // Grant FAN_ROLE to user, recording the assigner as granter
+    let grant = RoleGrant {
+        granted_at: env.block.time.seconds(),
+        granted_by: info.sender.clone(),
+        expires_at: None,
+    };
+    save_role(deps.storage, &user_addr, FAN_ROLE, &grant)?;
+    record_audit(deps.storage, &env, "assign_fan_role", &info.sender, &user_addr, FAN_ROLE, None)?;
 
     Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &user_addr, FAN_ROLE, None, true)?)
         .add_attribute("action", "assign_fan_role")
-        .add_attribute("user", user))
+        .add_attribute("user", user)
+        .add_attribute("assigner", info.sender))
 }
 
-pub fn pause(
+pub fn setRoleAdmin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: String,
+    admin_role: String,
+) -> Result<Response, ContractError> {
+    // Only the default admin can re-assign role admins
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_role_admin(deps.storage, &role, &admin_role)?;
+
+    // Rewiring who administers a role is as powerful as granting it
+    let previous_admin_role = get_role_admin(deps.storage, &role)?;
+    let action = ProposalAction::SetRoleAdmin { role: role.clone(), admin_role: admin_role.clone() };
+    if threshold_enabled(deps.storage)? {
+        return create_proposal(deps, env, info, action);
+    }
+    apply_proposal_action(deps, &env, &info.sender, &action)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_role_admin")
+        .add_attribute("role", role)
+        .add_attribute("previous_admin_role", previous_admin_role)
+        .add_attribute("admin_role", admin_role))
+}
+
+pub fn registerRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: String,
+    description: String,
+    admin_role: String,
+) -> Result<Response, ContractError> {
+    // Only the default admin can extend the role catalog
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_new_role(deps.storage, &role, &admin_role)?;
+
+    let action = ProposalAction::RegisterRole { role: role.clone(), description: description.clone(), admin_role: admin_role.clone() };
+    if threshold_enabled(deps.storage)? {
+        return create_proposal(deps, env, info, action);
+    }
+    apply_proposal_action(deps, &env, &info.sender, &action)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_role")
+        .add_attribute("role", role)
+        .add_attribute("admin_role", admin_role)
+        .add_attribute("description", description))
+}
+
+pub fn deprecateRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: String,
+) -> Result<Response, ContractError> {
+    // Only the default admin can retire roles
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if role == DEFAULT_ADMIN_ROLE {
+        return Err(ContractError::CustomError { message: "Cannot deprecate DEFAULT_ADMIN_ROLE".to_string() });
+    }
+
+    // Existing holders keep the role, it just can no longer be granted
+    let mut role_info = ROLE_REGISTRY.may_load(deps.storage, &role)?
+        .ok_or(ContractError::CustomError { message: "Role not registered".to_string() })?;
+    role_info.deprecated = true;
+    ROLE_REGISTRY.save(deps.storage, &role, &role_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deprecate_role")
+        .add_attribute("role", role))
+}
+
+pub fn proposeAdmin(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
+    new_admin: String,
 ) -> Result<Response, ContractError> {
     // Check if sender has admin role
-    if !has_role(deps.storage, &info.sender, DEFAULT_ADMIN_ROLE)? {
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Set PAUSED state to true
-    PAUSED.save(deps.storage, &true)?;
-    
+    let new_admin_addr = deps.api.addr_validate(&new_admin)?;
+    if has_role(deps.storage, &env, &new_admin_addr, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::CustomError { message: "Address is already an admin".to_string() });
+    }
+
+    // In threshold mode the handover only becomes acceptable once approved
+    let action = ProposalAction::ProposeAdmin { new_admin: new_admin_addr.to_string() };
+    if threshold_enabled(deps.storage)? {
+        return create_proposal(deps, env, info, action);
+    }
+    apply_proposal_action(deps, &env, &info.sender, &action)?;
+
     Ok(Response::new()
-        .add_attribute("action", "pause")
-        .add_attribute("sender", info.sender))
+        .add_attribute("action", "propose_admin")
+        .add_attribute("proposer", info.sender)
+        .add_attribute("new_admin", new_admin))
 }
 
-pub fn unpause(
+pub fn acceptAdmin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_ADMIN.may_load(deps.storage)?
+        .ok_or(ContractError::CustomError { message: "No pending admin".to_string() })?;
+
+    if info.sender != pending.new_admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The proposal dies with the proposer's admin rights
+    if !has_role(deps.storage, &env, &pending.proposer, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::CustomError { message: "Proposer is no longer an admin".to_string() });
+    }
+
+    // Grant before revoking so the admin count never drops to zero
+    let grant = RoleGrant {
+        granted_at: env.block.time.seconds(),
+        granted_by: pending.proposer.clone(),
+        expires_at: None,
+    };
+    save_role(deps.storage, &pending.new_admin, DEFAULT_ADMIN_ROLE, &grant)?;
+    remove_role(deps.storage, &pending.proposer, DEFAULT_ADMIN_ROLE)?;
+    PENDING_ADMIN.remove(deps.storage);
+    record_audit(deps.storage, &env, "accept_admin", &info.sender, &pending.new_admin, DEFAULT_ADMIN_ROLE, None)?;
+    record_audit(deps.storage, &env, "accept_admin", &info.sender, &pending.proposer, DEFAULT_ADMIN_ROLE, None)?;
+
+    Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &pending.new_admin, DEFAULT_ADMIN_ROLE, None, true)?)
+        .add_submessages(role_changed_msgs(deps.storage, &pending.proposer, DEFAULT_ADMIN_ROLE, None, false)?)
+        .add_attribute("action", "accept_admin")
+        .add_attribute("previous_admin", pending.proposer)
+        .add_attribute("new_admin", pending.new_admin))
+}
+
+pub fn batchGrantRoles(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    grants: Vec<RoleAssignment>,
+) -> Result<Response, ContractError> {
+    validate_batch_size(deps.storage, grants.len())?;
+
+    // Validate every item before writing anything
+    let mut validated = Vec::with_capacity(grants.len());
+    for assignment in grants {
+        only_role_admin(deps.storage, &env, &info.sender, &assignment.role)?;
+        ensure_grantable(deps.storage, &assignment.role)?;
+        ensure_not_fan_assigner(&assignment.role)?;
+        ensure_direct_change_allowed(deps.storage, &assignment.role)?;
+        let user_addr = deps.api.addr_validate(&assignment.user)?;
+        validated.push((user_addr, assignment.role));
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "batch_grant_roles")
+        .add_attribute("count", validated.len().to_string());
+
+    for (user_addr, role) in validated {
+        consume_fan_allowance(deps.storage, &env, &info.sender, &role)?;
+        let grant = RoleGrant {
+            granted_at: env.block.time.seconds(),
+            granted_by: info.sender.clone(),
+            expires_at: None,
+        };
+        save_role(deps.storage, &user_addr, &role, &grant)?;
+        record_audit(deps.storage, &env, "batch_grant_roles", &info.sender, &user_addr, &role, None)?;
+        response = response
+            .add_submessages(role_changed_msgs(deps.storage, &user_addr, &role, None, true)?)
+            .add_attribute("grant", format!("{}:{}", user_addr, role));
+    }
+
+    Ok(response)
+}
+
+pub fn batchRevokeRoles(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    revocations: Vec<RoleAssignment>,
+) -> Result<Response, ContractError> {
+    validate_batch_size(deps.storage, revocations.len())?;
+
+    // Validate every item before writing anything
+    let mut validated = Vec::with_capacity(revocations.len());
+    for assignment in revocations {
+        only_role_admin(deps.storage, &env, &info.sender, &assignment.role)?;
+        ensure_direct_change_allowed(deps.storage, &assignment.role)?;
+        let user_addr = deps.api.addr_validate(&assignment.user)?;
+        validated.push((user_addr, assignment.role));
+    }
+
+    let mut response = Response::new()
+        .add_attribute("action", "batch_revoke_roles")
+        .add_attribute("count", validated.len().to_string());
+
+    // The last-admin check inside remove_role still aborts the whole batch
+    for (user_addr, role) in validated {
+        remove_role(deps.storage, &user_addr, &role)?;
+        record_audit(deps.storage, &env, "batch_revoke_roles", &info.sender, &user_addr, &role, None)?;
+        response = response
+            .add_submessages(role_changed_msgs(deps.storage, &user_addr, &role, None, false)?)
+            .add_attribute("revoke", format!("{}:{}", user_addr, role));
+    }
+
+    Ok(response)
+}
+
+pub fn updateConfig(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
+    max_batch_size: Option<u32>,
 ) -> Result<Response, ContractError> {
     // Check if sender has admin role
-    if !has_role(deps.storage, &info.sender, DEFAULT_ADMIN_ROLE)? {
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Set PAUSED state to false
-    PAUSED.save(deps.storage, &false)?;
-    
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(max_batch_size) = max_batch_size {
+        if max_batch_size == 0 {
+            return Err(ContractError::CustomError { message: "Max batch size must be positive".to_string() });
+        }
+        config.max_batch_size = max_batch_size;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
     Ok(Response::new()
-        .add_attribute("action", "unpause")
-        .add_attribute("sender", info.sender))
+        .add_attribute("action", "update_config")
+        .add_attribute("max_batch_size", config.max_batch_size.to_string()))
 }
 
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::HasRole { user, role } => to_json_binary(&query_has_role(deps, user, role)?),
-        QueryMsg::HasAnyRole { user, roles } => to_json_binary(&has_any_role(deps, user, roles)?),
-        QueryMsg::HasAllRoles { user, roles } => to_json_binary(&has_all_roles(deps, user, roles)?),
-        QueryMsg::GetUserRoles { user } => to_json_binary(&get_user_roles(deps, user)?),
+pub fn updateSecurity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sensitive_roles: Vec<String>,
+    approval_threshold: u32,
+    voting_period: u64,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_security(deps.storage, &sensitive_roles, approval_threshold, voting_period)?;
+
+    // Once threshold mode is on, loosening it needs the same approvals
+    let action = ProposalAction::UpdateSecurity { sensitive_roles, approval_threshold, voting_period };
+    if threshold_enabled(deps.storage)? {
+        return create_proposal(deps, env, info, action);
     }
+
+    let msgs = apply_proposal_action(deps, &env, &info.sender, &action)?;
+
+    Ok(Response::new()
+        .add_submessages(msgs)
+        .add_attribute("action", "update_security")
+        .add_attribute("approval_threshold", approval_threshold.to_string())
+        .add_attribute("voting_period", voting_period.to_string()))
 }
 
-fn query_has_role(deps: Deps, user: String, role: String) -> StdResult<BoolResponse> {
-    let user_addr = deps.api.addr_validate(&user)?;
-    let result = has_role(deps.storage, &user_addr, &role)?;
-    Ok(BoolResponse { result })
+pub fn approveProposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    // Only admins vote on proposals
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = load_open_proposal(deps.storage, &env, proposal_id)?;
+    if proposal.approvals.contains(&info.sender) {
+        return Err(ContractError::CustomError { message: "Already approved".to_string() });
+    }
+    proposal.approvals.push(info.sender.clone());
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "approve_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("approver", info.sender)
+        .add_attribute("approvals", proposal.approvals.len().to_string()))
 }
 
-fn has_any_role(deps: Deps, user: String, roles: Vec<String>) -> StdResult<BoolResponse> {
-    let user_addr = deps.api.addr_validate(&user)?;
-    
-    for role in roles {
-        if has_role(deps.storage, &user_addr, &role)? {
-            return Ok(BoolResponse { result: true });
+pub fn executeProposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = load_open_proposal(deps.storage, &env, proposal_id)?;
+
+    // Approvals from addresses that have since lost admin rights no longer count
+    let mut approvals = 0u32;
+    for approver in &proposal.approvals {
+        if has_role(deps.storage, &env, approver, DEFAULT_ADMIN_ROLE)? {
+            approvals += 1;
         }
     }
-    
-    Ok(BoolResponse { result: false })
+    let threshold = CONFIG.load(deps.storage)?.approval_threshold;
+    if approvals < threshold {
+        return Err(ContractError::CustomError { message: format!("Not enough approvals: {}/{}", approvals, threshold) });
+    }
+
+    proposal.status = ProposalStatus::Executed;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    let messages = apply_proposal_action(deps, &env, &proposal.proposer, &proposal.action)?;
+
+    Ok(Response::new()
+        .add_submessages(messages)
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("executor", info.sender))
+}
+
+pub fn cancelProposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal_id: u64,
+) -> Result<Response, ContractError> {
+    let mut proposal = PROPOSALS.may_load(deps.storage, proposal_id)?
+        .ok_or(ContractError::CustomError { message: "Proposal not found".to_string() })?;
+
+    // The proposer or any admin may withdraw a proposal
+    if info.sender != proposal.proposer && !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    if proposal.status != ProposalStatus::Open {
+        return Err(ContractError::CustomError { message: "Proposal is not open".to_string() });
+    }
+
+    proposal.status = ProposalStatus::Cancelled;
+    PROPOSALS.save(deps.storage, proposal_id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_proposal")
+        .add_attribute("proposal_id", proposal_id.to_string())
+        .add_attribute("cancelled_by", info.sender))
 }
 
-fn has_all_roles(deps: Deps, user: String, roles: Vec<String>) -> StdResult<BoolResponse> {
+pub fn grantScopedRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    role: String,
+    scope: String,
+    expires_at: Option<u64>,
+) -> Result<Response, ContractError> {
+    // Check if sender holds the admin role of the role being granted
+    only_role_admin(deps.storage, &env, &info.sender, &role)?;
+    ensure_grantable(deps.storage, &role)?;
+    ensure_not_fan_assigner(&role)?;
+    if role == DEFAULT_ADMIN_ROLE {
+        return Err(ContractError::CustomError { message: "DEFAULT_ADMIN_ROLE cannot be scoped".to_string() });
+    }
+    if scope.is_empty() {
+        return Err(ContractError::CustomError { message: "Scope cannot be empty".to_string() });
+    }
+
     let user_addr = deps.api.addr_validate(&user)?;
-    
-    for role in roles {
-        if !has_role(deps.storage, &user_addr, &role)? {
-            return Ok(BoolResponse { result: false });
-        }
+    validate_expiry(&env, &role, expires_at)?;
+
+    // A sensitive role needs approvals whatever its scope
+    let action = ProposalAction::GrantScopedRole { user: user_addr.to_string(), role: role.clone(), scope: scope.clone(), expires_at };
+    if requires_approval(deps.storage, &role)? {
+        return create_proposal(deps, env, info, action);
     }
-    
-    Ok(BoolResponse { result: true })
+    let messages = apply_proposal_action(deps, &env, &info.sender, &action)?;
+
+    Ok(Response::new()
+        .add_submessages(messages)
+        .add_attribute("action", "grant_scoped_role")
+        .add_attribute("user", user)
+        .add_attribute("role", role)
+        .add_attribute("scope", scope)
+        .add_attribute("expires_at", format_expiry(expires_at)))
 }
 
-fn get_user_roles(deps: Deps, user: String) -> StdResult<RolesResponse> {
+pub fn revokeScopedRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    role: String,
+    scope: String,
+) -> Result<Response, ContractError> {
+    // Check if sender holds the admin role of the role being revoked
+    only_role_admin(deps.storage, &env, &info.sender, &role)?;
+
     let user_addr = deps.api.addr_validate(&user)?;
-    
-    // Define potential roles to check
-    let all_roles = vec![
-        DEFAULT_ADMIN_ROLE.to_string(),
-        FAN_ROLE.to_string(),
-        ORGANIZER_ROLE.to_string(),
-        ARTIST_ROLE.to_string(),
-        BRAND_ROLE.to_string(),
-        MODERATOR_ROLE.to_string(),
-        FAN_ASSIGNER_ROLE.to_string(),
-    ];
-    
-    let mut user_roles = Vec::new();
-    for role in all_roles {
-        if has_role(deps.storage, &user_addr, &role)? {
-            user_roles.push(role);
-        }
+    if !SCOPED_ROLES.has(deps.storage, (user_addr.as_str(), role.as_str(), scope.as_str())) {
+        return Err(ContractError::CustomError { message: "Scoped role not granted".to_string() });
     }
-    
-    Ok(RolesResponse { roles: user_roles })
+
+    let action = ProposalAction::RevokeScopedRole { user: user_addr.to_string(), role: role.clone(), scope: scope.clone() };
+    if requires_approval(deps.storage, &role)? {
+        return create_proposal(deps, env, info, action);
+    }
+    let messages = apply_proposal_action(deps, &env, &info.sender, &action)?;
+
+    Ok(Response::new()
+        .add_submessages(messages)
+        .add_attribute("action", "revoke_scoped_role")
+        .add_attribute("user", user)
+        .add_attribute("role", role)
+        .add_attribute("scope", scope))
 }
 
-// Helper to check if an address has a role
-pub fn has_role(storage: &dyn cosmwasm_std::Storage, user: &Addr, role: &str) -> StdResult<bool> {
-    Ok(ROLES.may_load(storage, (user.as_str(), role))?.unwrap_or(false))
+pub fn registerVoucherSigner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+    pubkey: Binary,
+    key_type: KeyType,
+    roles: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Vouchers cannot hand out roles that need multisig approval
+    for role in &roles {
+        ensure_grantable(deps.storage, role)?;
+        ensure_direct_change_allowed(deps.storage, role)?;
+    }
+
+    let signer = VoucherSigner {
+        pubkey,
+        key_type,
+        roles,
+        registered_by: info.sender.clone(),
+    };
+    VOUCHER_SIGNERS.save(deps.storage, &name, &signer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_voucher_signer")
+        .add_attribute("name", name)
+        .add_attribute("roles", signer.roles.join(",")))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::testing::{mock_dependencies, mock_env, mock_info};
+pub fn removeVoucherSigner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    name: String,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    #[test]
-    fn proper_initialization() {
-        let mut deps = mock_dependencies();
-        let info = mock_info("creator", &[]);
-        
-        // Instantiate the contract
-        let res = instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
-        assert_eq!(0, res.messages.len());
-        
-        // Verify creator has admin role
-        let has_role = query_has_role(
-            deps.as_ref(),
-            "creator".to_string(),
-            DEFAULT_ADMIN_ROLE.to_string(),
-        )
-        .unwrap();
-        assert_eq!(true, has_role.result);
+    if !VOUCHER_SIGNERS.has(deps.storage, &name) {
+        return Err(ContractError::CustomError { message: "Voucher signer not found".to_string() });
     }
+    VOUCHER_SIGNERS.remove(deps.storage, &name);
 
-    #[test]
-    fn test_grant_role() {
-        let mut deps = mock_dependencies();
-        let admin_info = mock_info("admin", &[]);
-        
-        // Instantiate contract with admin
-        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
-        
-        // Grant ARTIST_ROLE to user
-        let res = grantRole(
-            deps.as_mut(),
-            admin_info,
-            "user".to_string(),
-            ARTIST_ROLE.to_string(),
-        )
-        .unwrap();
-        
-        assert_eq!("grant_role", res.attributes[0].value);
-        
-        // Verify user has ARTIST_ROLE
+    Ok(Response::new()
+        .add_attribute("action", "remove_voucher_signer")
+        .add_attribute("name", name))
+}
+
+pub fn claimRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    signer_name: String,
+    voucher: RoleVoucher,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let RoleVoucher { role, expires_at, nonce, max_claims } = voucher.clone();
+    if is_blocked(deps.storage, &env, &info.sender)? {
+        return Err(ContractError::Blocked {});
+    }
+    if env.block.time.seconds() >= expires_at {
+        return Err(ContractError::CustomError { message: "Voucher expired".to_string() });
+    }
+    ensure_grantable(deps.storage, &role)?;
+    ensure_not_fan_assigner(&role)?;
+    ensure_direct_change_allowed(deps.storage, &role)?;
+
+    // Only the named signer's key is checked, so claims cost one verification
+    let signer = VOUCHER_SIGNERS.may_load(deps.storage, &signer_name)?
+        .ok_or(ContractError::CustomError { message: "Voucher signer not registered".to_string() })?;
+    if !signer.roles.contains(&role) {
+        return Err(ContractError::CustomError { message: format!("Signer cannot issue vouchers for {}", role) });
+    }
+    let digest = voucher_digest(&env, &voucher);
+    if !verify_voucher_signature(deps.as_ref(), &signer, &digest, &signature) {
+        return Err(ContractError::CustomError { message: "Invalid voucher signature".to_string() });
+    }
+
+    // Replay protection: one claim per user, at most `max_claims` per voucher
+    let claimed_key = (signer_name.as_str(), nonce, info.sender.as_str());
+    if VOUCHER_CLAIMED.has(deps.storage, claimed_key) {
+        return Err(ContractError::CustomError { message: "Voucher already claimed".to_string() });
+    }
+    let claims = VOUCHER_CLAIM_COUNT.may_load(deps.storage, (&signer_name, nonce))?.unwrap_or(0);
+    if claims >= max_claims {
+        return Err(ContractError::CustomError { message: "Voucher fully claimed".to_string() });
+    }
+    VOUCHER_CLAIM_COUNT.save(deps.storage, (&signer_name, nonce), &(claims + 1))?;
+    VOUCHER_CLAIMED.save(deps.storage, claimed_key, &true)?;
+
+    let grant = RoleGrant {
+        granted_at: env.block.time.seconds(),
+        granted_by: env.contract.address.clone(),
+        expires_at: None,
+    };
+    save_role(deps.storage, &info.sender, &role, &grant)?;
+    record_audit(deps.storage, &env, "claim_role", &info.sender, &info.sender, &role, None)?;
+
+    Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &info.sender, &role, None, true)?)
+        .add_attribute("action", "claim_role")
+        .add_attribute("user", info.sender)
+        .add_attribute("role", role)
+        .add_attribute("signer", signer_name)
+        .add_attribute("nonce", nonce.to_string()))
+}
+
+pub fn registerSessionKey(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    session: String,
+    actions: Vec<String>,
+    expires_at: u64,
+) -> Result<Response, ContractError> {
+    if is_blocked(deps.storage, &env, &info.sender)? {
+        return Err(ContractError::Blocked {});
+    }
+
+    let session_addr = deps.api.addr_validate(&session)?;
+    if session_addr == info.sender {
+        return Err(ContractError::CustomError { message: "Session key must differ from owner".to_string() });
+    }
+    if actions.is_empty() {
+        return Err(ContractError::CustomError { message: "Session key needs at least one action".to_string() });
+    }
+    if expires_at <= env.block.time.seconds() {
+        return Err(ContractError::CustomError { message: "Session expiry must be in the future".to_string() });
+    }
+
+    // Re-registering the same session replaces its actions and expiry
+    let session_key = SessionKey {
+        session: session_addr.clone(),
+        actions: actions.clone(),
+        expires_at,
+        created_at: env.block.time.seconds(),
+    };
+    SESSION_KEYS.save(deps.storage, (info.sender.as_str(), session_addr.as_str()), &session_key)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_session_key")
+        .add_attribute("owner", info.sender)
+        .add_attribute("session", session_addr)
+        .add_attribute("actions", actions.join(","))
+        .add_attribute("expires_at", expires_at.to_string()))
+}
+
+pub fn revokeSessionKey(deps: DepsMut, info: MessageInfo, session: String) -> Result<Response, ContractError> {
+    let session_addr = deps.api.addr_validate(&session)?;
+    if !SESSION_KEYS.has(deps.storage, (info.sender.as_str(), session_addr.as_str())) {
+        return Err(ContractError::CustomError { message: "Session key not found".to_string() });
+    }
+    SESSION_KEYS.remove(deps.storage, (info.sender.as_str(), session_addr.as_str()));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_session_key")
+        .add_attribute("owner", info.sender)
+        .add_attribute("session", session_addr))
+}
+
+pub fn setBadgeUri(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: String,
+    token_uri: Option<String>,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !BADGE_ROLES.contains(&role.as_str()) {
+        return Err(ContractError::CustomError { message: format!("Role has no badge: {}", role) });
+    }
+    match &token_uri {
+        Some(token_uri) => BADGE_URIS.save(deps.storage, &role, token_uri)?,
+        None => BADGE_URIS.remove(deps.storage, &role),
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_badge_uri")
+        .add_attribute("role", role)
+        .add_attribute("token_uri", token_uri.unwrap_or_default()))
+}
+
+pub fn applyForRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    role: String,
+    evidence_uri: String,
+) -> Result<Response, ContractError> {
+    if is_blocked(deps.storage, &env, &info.sender)? {
+        return Err(ContractError::Blocked {});
+    }
+    if role == DEFAULT_ADMIN_ROLE {
+        return Err(ContractError::CustomError { message: "Cannot apply for DEFAULT_ADMIN_ROLE".to_string() });
+    }
+    ensure_grantable(deps.storage, &role)?;
+    if has_role(deps.storage, &env, &info.sender, &role)? {
+        return Err(ContractError::CustomError { message: "Role already held".to_string() });
+    }
+
+    // Rejected applicants may re-apply; pending ones must wait for review
+    let key = (role.as_str(), info.sender.as_str());
+    if let Some(existing) = APPLICATIONS.may_load(deps.storage, key)? {
+        if existing.status == ApplicationStatus::Pending {
+            return Err(ContractError::CustomError { message: "Application already pending".to_string() });
+        }
+    }
+
+    let application = Application {
+        applicant: info.sender.clone(),
+        role: role.clone(),
+        evidence_uri,
+        status: ApplicationStatus::Pending,
+        submitted_at: env.block.time.seconds(),
+        reviewed_by: None,
+        reviewed_at: None,
+        reason: None,
+    };
+    APPLICATIONS.save(deps.storage, key, &application)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "apply_for_role")
+        .add_attribute("applicant", info.sender)
+        .add_attribute("role", role))
+}
+
+pub fn approveApplication(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    applicant: String,
+    role: String,
+) -> Result<Response, ContractError> {
+    // Reviewers are holders of the role's admin role
+    only_role_admin(deps.storage, &env, &info.sender, &role)?;
+    ensure_grantable(deps.storage, &role)?;
+    ensure_not_fan_assigner(&role)?;
+    ensure_direct_change_allowed(deps.storage, &role)?;
+
+    let applicant_addr = deps.api.addr_validate(&applicant)?;
+    let mut application = load_pending_application(deps.storage, &applicant_addr, &role)?;
+    if is_blocked(deps.storage, &env, &applicant_addr)? {
+        return Err(ContractError::Blocked {});
+    }
+
+    application.status = ApplicationStatus::Approved;
+    application.reviewed_by = Some(info.sender.clone());
+    application.reviewed_at = Some(env.block.time.seconds());
+    APPLICATIONS.save(deps.storage, (role.as_str(), applicant_addr.as_str()), &application)?;
+    consume_fan_allowance(deps.storage, &env, &info.sender, &role)?;
+
+    let grant = RoleGrant {
+        granted_at: env.block.time.seconds(),
+        granted_by: info.sender.clone(),
+        expires_at: None,
+    };
+    save_role(deps.storage, &applicant_addr, &role, &grant)?;
+    record_audit(deps.storage, &env, "approve_application", &info.sender, &applicant_addr, &role, None)?;
+
+    Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &applicant_addr, &role, None, true)?)
+        .add_attribute("action", "approve_application")
+        .add_attribute("applicant", applicant)
+        .add_attribute("role", role))
+}
+
+pub fn rejectApplication(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    applicant: String,
+    role: String,
+    reason: String,
+) -> Result<Response, ContractError> {
+    // Reviewers are holders of the role's admin role
+    only_role_admin(deps.storage, &env, &info.sender, &role)?;
+
+    let applicant_addr = deps.api.addr_validate(&applicant)?;
+    let mut application = load_pending_application(deps.storage, &applicant_addr, &role)?;
+
+    application.status = ApplicationStatus::Rejected;
+    application.reviewed_by = Some(info.sender.clone());
+    application.reviewed_at = Some(env.block.time.seconds());
+    application.reason = Some(reason.clone());
+    APPLICATIONS.save(deps.storage, (role.as_str(), applicant_addr.as_str()), &application)?;
+    record_audit(deps.storage, &env, "reject_application", &info.sender, &applicant_addr, &role, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "reject_application")
+        .add_attribute("applicant", applicant)
+        .add_attribute("role", role)
+        .add_attribute("reason", reason))
+}
+
+pub fn block(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    reason: String,
+    until: Option<u64>,
+) -> Result<Response, ContractError> {
+    if !can_perform(deps.storage, &env, &info.sender, ACTION_BLOCK, None)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let user_addr = deps.api.addr_validate(&user)?;
+    // Moderators must not be able to lock out the admins
+    if has_role(deps.storage, &env, &user_addr, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::CustomError { message: "Cannot block an admin".to_string() });
+    }
+    if until.is_some_and(|until| until <= env.block.time.seconds()) {
+        return Err(ContractError::CustomError { message: "Block end must be in the future".to_string() });
+    }
+
+    let entry = BlockEntry {
+        reason: reason.clone(),
+        until,
+        blocked_by: info.sender.clone(),
+        blocked_at: env.block.time.seconds(),
+    };
+    BLOCKLIST.save(deps.storage, user_addr.as_str(), &entry)?;
+    record_audit(deps.storage, &env, "block", &info.sender, &user_addr, AUDIT_ROLE_BLOCKLIST, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "block")
+        .add_attribute("user", user)
+        .add_attribute("reason", reason)
+        .add_attribute("until", format_expiry(until)))
+}
+
+pub fn unblock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+) -> Result<Response, ContractError> {
+    if !can_perform(deps.storage, &env, &info.sender, ACTION_BLOCK, None)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let user_addr = deps.api.addr_validate(&user)?;
+    if !BLOCKLIST.has(deps.storage, user_addr.as_str()) {
+        return Err(ContractError::CustomError { message: "User is not blocked".to_string() });
+    }
+    BLOCKLIST.remove(deps.storage, user_addr.as_str());
+    record_audit(deps.storage, &env, "unblock", &info.sender, &user_addr, AUDIT_ROLE_BLOCKLIST, None)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unblock")
+        .add_attribute("user", user))
+}
+
+pub fn setPermission(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: String,
+    roles: Vec<String>,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_permission(deps.storage, &action, &roles)?;
+
+    // Mapping an action to a role hands that power to every holder
+    let roles_attr = roles.join(",");
+    let proposal_action = ProposalAction::SetPermission { action: action.clone(), roles };
+    if threshold_enabled(deps.storage)? {
+        return create_proposal(deps, env, info, proposal_action);
+    }
+    apply_proposal_action(deps, &env, &info.sender, &proposal_action)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_permission")
+        .add_attribute("permission", action)
+        .add_attribute("roles", roles_attr))
+}
+
+pub fn removePermission(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: String,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !PERMISSIONS.has(deps.storage, &action) {
+        return Err(ContractError::CustomError { message: format!("Unknown action: {}", action) });
+    }
+    PERMISSIONS.remove(deps.storage, &action);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_permission")
+        .add_attribute("permission", action))
+}
+
+pub fn addHook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    if HOOKS.has(deps.storage, contract_addr.as_str()) {
+        return Err(ContractError::CustomError { message: "Hook already registered".to_string() });
+    }
+    HOOKS.save(deps.storage, contract_addr.as_str(), &true)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_hook")
+        .add_attribute("contract", contract))
+}
+
+pub fn removeHook(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    if !HOOKS.has(deps.storage, contract_addr.as_str()) {
+        return Err(ContractError::CustomError { message: "Hook not registered".to_string() });
+    }
+    HOOKS.remove(deps.storage, contract_addr.as_str());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_hook")
+        .add_attribute("contract", contract))
+}
+
+pub fn clearExpiredRole(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: String,
+    role: String,
+    scope: Option<String>,
+) -> Result<Response, ContractError> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let grant = match &scope {
+        Some(scope) => SCOPED_ROLES.may_load(deps.storage, (user_addr.as_str(), &role, scope))?,
+        None => ROLES.may_load(deps.storage, (user_addr.as_str(), &role))?,
+    }
+    .ok_or(ContractError::CustomError { message: "Role not granted".to_string() })?;
+    if grant.is_active(env.block.time.seconds()) {
+        return Err(ContractError::CustomError { message: "Role has not expired".to_string() });
+    }
+
+    match &scope {
+        Some(scope) => SCOPED_ROLES.remove(deps.storage, (user_addr.as_str(), &role, scope)),
+        None => remove_role(deps.storage, &user_addr, &role)?,
+    }
+    record_audit(deps.storage, &env, "clear_expired_role", &info.sender, &user_addr, &role, scope.as_deref())?;
+
+    Ok(Response::new()
+        .add_submessages(role_changed_msgs(deps.storage, &user_addr, &role, scope.as_deref(), false)?)
+        .add_attribute("action", "clear_expired_role")
+        .add_attribute("user", user)
+        .add_attribute("role", role)
+        .add_attribute("scope", scope.unwrap_or_default()))
+}
+
+pub fn pause(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    scope: Option<String>,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Pause a single scope, or the whole contract when no scope is given
+    match &scope {
+        Some(scope) => {
+            validate_pause_scope(scope)?;
+            PAUSED_SCOPES.save(deps.storage, scope, &true)?;
+        },
+        None => PAUSED.save(deps.storage, &true)?,
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "pause")
+        .add_attribute("scope", scope.unwrap_or_else(|| "all".to_string()))
+        .add_attribute("sender", info.sender))
+}
+
+pub fn unpause(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    scope: Option<String>,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Unpause a single scope, or lift the global pause when no scope is given
+    match &scope {
+        Some(scope) => {
+            validate_pause_scope(scope)?;
+            PAUSED_SCOPES.remove(deps.storage, scope);
+        },
+        None => PAUSED.save(deps.storage, &false)?,
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "unpause")
+        .add_attribute("scope", scope.unwrap_or_else(|| "all".to_string()))
+        .add_attribute("sender", info.sender))
+}
+
+pub fn registerPlatformContract(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    name: String,
+    kind: PlatformContractKind,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    let platform_contract = PlatformContract { name: name.clone(), kind };
+    PLATFORM_CONTRACTS.save(deps.storage, contract_addr.as_str(), &platform_contract)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_platform_contract")
+        .add_attribute("contract", contract)
+        .add_attribute("name", name))
+}
+
+pub fn removePlatformContract(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let contract_addr = deps.api.addr_validate(&contract)?;
+    if !PLATFORM_CONTRACTS.has(deps.storage, contract_addr.as_str()) {
+        return Err(ContractError::CustomError { message: "Platform contract not registered".to_string() });
+    }
+    PLATFORM_CONTRACTS.remove(deps.storage, contract_addr.as_str());
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_platform_contract")
+        .add_attribute("contract", contract))
+}
+
+// Guardians can halt everything; only admins can bring it back
+pub fn pauseAll(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    if !can_perform(deps.storage, &env, &info.sender, ACTION_PAUSE_ALL, None)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &true)?;
+    let msgs = platform_pause_msgs(deps.storage, true)?;
+
+    Ok(Response::new()
+        .add_submessages(msgs)
+        .add_attribute("action", "pause_all")
+        .add_attribute("sender", info.sender))
+}
+
+pub fn unpauseAll(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    // Check if sender has admin role
+    if !has_role(deps.storage, &env, &info.sender, DEFAULT_ADMIN_ROLE)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &false)?;
+    let msgs = platform_pause_msgs(deps.storage, false)?;
+
+    Ok(Response::new()
+        .add_submessages(msgs)
+        .add_attribute("action", "unpause_all")
+        .add_attribute("sender", info.sender))
+}
+
+fn platform_pause_msgs(storage: &dyn cosmwasm_std::Storage, paused: bool) -> StdResult<Vec<SubMsg>> {
+    PLATFORM_CONTRACTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (contract, platform_contract) = item?;
+            let msg = match (platform_contract.kind, paused) {
+                (PlatformContractKind::PostMinter | PlatformContractKind::TribeController, true) => PlatformPauseMsg::Pause {},
+                (PlatformContractKind::PostMinter | PlatformContractKind::TribeController, false) => PlatformPauseMsg::Unpause {},
+                (PlatformContractKind::ProfileNftMinter, paused) => PlatformPauseMsg::SetPaused { paused },
+            };
+            let execute = WasmMsg::Execute {
+                contract_addr: contract,
+                msg: to_json_binary(&msg)?,
+                funds: vec![],
+            };
+            Ok(SubMsg::reply_on_error(execute, PLATFORM_PAUSE_REPLY_ID))
+        })
+        .collect()
+}
+
+// Rejects messages blocked by the global pause or by the pause scope they belong to
+fn ensure_not_paused(storage: &dyn cosmwasm_std::Storage, msg: &ExecuteMsg) -> Result<(), ContractError> {
+    let scopes = match msg {
+        // Admin recovery stays available while paused
+        ExecuteMsg::Pause { .. }
+        | ExecuteMsg::Unpause { .. }
+        | ExecuteMsg::PauseAll {}
+        | ExecuteMsg::UnpauseAll {}
+        | ExecuteMsg::RevokeRole { .. }
+        | ExecuteMsg::BatchRevokeRoles { .. }
+        | ExecuteMsg::RevokeFanAssigner { .. }
+        | ExecuteMsg::ProposeAdmin { .. }
+        | ExecuteMsg::AcceptAdmin {}
+        | ExecuteMsg::CancelProposal { .. }
+        | ExecuteMsg::RevokeSessionKey { .. }
+        | ExecuteMsg::Block { .. }
+        | ExecuteMsg::Unblock { .. } => return Ok(()),
+        ExecuteMsg::GrantRole { role, .. }
+        | ExecuteMsg::ExtendRole { role, .. }
+        | ExecuteMsg::GrantScopedRole { role, .. }
+        | ExecuteMsg::ClaimRole { role, .. }
+        | ExecuteMsg::ApproveApplication { role, .. } => vec![grant_pause_scope(role)],
+        ExecuteMsg::BatchGrantRoles { grants } => grants.iter().map(|grant| grant_pause_scope(&grant.role)).collect(),
+        ExecuteMsg::RevokeScopedRole { .. } => vec![PAUSE_SCOPE_GRANTS],
+        ExecuteMsg::AuthorizeFanAssigner { .. } | ExecuteMsg::AssignFanRole { .. } => vec![PAUSE_SCOPE_FAN_ASSIGNMENT],
+        // A proposal is held to the scope of the message it stands in for
+        ExecuteMsg::ExecuteProposal { proposal_id } => match PROPOSALS.may_load(storage, *proposal_id)?.map(|proposal| proposal.action) {
+            Some(ProposalAction::GrantRole { role, .. })
+            | Some(ProposalAction::ExtendRole { role, .. })
+            | Some(ProposalAction::GrantScopedRole { role, .. }) => vec![grant_pause_scope(&role)],
+            Some(ProposalAction::RevokeScopedRole { .. }) => vec![PAUSE_SCOPE_GRANTS],
+            _ => vec![],
+        },
+        _ => vec![],
+    };
+
+    if PAUSED.may_load(storage)?.unwrap_or(false) {
+        return Err(ContractError::CustomError { message: "Contract is paused".to_string() });
+    }
+
+    for scope in scopes {
+        if PAUSED_SCOPES.may_load(storage, scope)?.unwrap_or(false) {
+            return Err(ContractError::CustomError { message: format!("Paused: {}", scope) });
+        }
+    }
+    Ok(())
+}
+
+// Fan roles are frozen with fan assignment, whichever message grants them
+fn grant_pause_scope(role: &str) -> &'static str {
+    if role == FAN_ROLE || role == FAN_ASSIGNER_ROLE {
+        PAUSE_SCOPE_FAN_ASSIGNMENT
+    } else {
+        PAUSE_SCOPE_GRANTS
+    }
+}
+
+// sha256("{contract}:{chain_id}:{role}:{expires_at}:{nonce}:{max_claims}"), binding the voucher to this deployment
+pub fn voucher_digest(env: &Env, voucher: &RoleVoucher) -> Vec<u8> {
+    let payload = format!(
+        "{}:{}:{}:{}:{}:{}",
+        env.contract.address, env.block.chain_id, voucher.role, voucher.expires_at, voucher.nonce, voucher.max_claims
+    );
+    Sha256::digest(payload.as_bytes()).to_vec()
+}
+
+fn verify_voucher_signature(deps: Deps, signer: &VoucherSigner, digest: &[u8], signature: &Binary) -> bool {
+    let verified = match signer.key_type {
+        KeyType::Secp256k1 => deps.api.secp256k1_verify(digest, signature, &signer.pubkey),
+        KeyType::Ed25519 => deps.api.ed25519_verify(digest, signature, &signer.pubkey),
+    };
+    // Malformed keys or signatures simply don't match
+    verified.unwrap_or(false)
+}
+
+fn record_audit(
+    storage: &mut dyn cosmwasm_std::Storage,
+    env: &Env,
+    action: &str,
+    actor: &Addr,
+    target: &Addr,
+    role: &str,
+    scope: Option<&str>,
+) -> StdResult<()> {
+    let id = NEXT_AUDIT_ID.may_load(storage)?.unwrap_or(1);
+    NEXT_AUDIT_ID.save(storage, &(id + 1))?;
+
+    let entry = AuditEntry {
+        id,
+        action: action.to_string(),
+        actor: actor.clone(),
+        target: target.clone(),
+        role: role.to_string(),
+        scope: scope.map(str::to_string),
+        block_height: env.block.height,
+        timestamp: env.block.time.seconds(),
+    };
+    audit_log().save(storage, id, &entry)
+}
+
+// Build a RoleChanged notification for every registered hook; failures are caught in `reply`
+fn role_changed_msgs(
+    storage: &dyn cosmwasm_std::Storage,
+    user: &Addr,
+    role: &str,
+    scope: Option<&str>,
+    granted: bool,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = to_json_binary(&RoleHookMsg::RoleChanged {
+        user: user.to_string(),
+        role: role.to_string(),
+        scope: scope.map(str::to_string),
+        granted,
+    })?;
+
+    HOOKS
+        .keys(storage, None, None, Order::Ascending)
+        .map(|contract| {
+            let execute = WasmMsg::Execute {
+                contract_addr: contract?,
+                msg: msg.clone(),
+                funds: vec![],
+            };
+            Ok(SubMsg::reply_on_error(execute, HOOK_REPLY_ID))
+        })
+        .collect()
+}
+
+// Whether changes to `role` must go through a proposal
+fn requires_approval(storage: &dyn cosmwasm_std::Storage, role: &str) -> StdResult<bool> {
+    let config = CONFIG.load(storage)?;
+    Ok(config.approval_threshold > 0 && config.sensitive_roles.iter().any(|sensitive| sensitive == role))
+}
+
+fn ensure_direct_change_allowed(storage: &dyn cosmwasm_std::Storage, role: &str) -> Result<(), ContractError> {
+    if requires_approval(storage, role)? {
+        return Err(ContractError::CustomError { message: format!("Role requires a proposal: {}", role) });
+    }
+    Ok(())
+}
+
+fn create_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: ProposalAction,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let id = NEXT_PROPOSAL_ID.may_load(deps.storage)?.unwrap_or(1);
+    NEXT_PROPOSAL_ID.save(deps.storage, &(id + 1))?;
+
+    // The proposer's own approval is counted up front
+    let proposal = Proposal {
+        id,
+        action,
+        proposer: info.sender.clone(),
+        approvals: vec![info.sender.clone()],
+        status: ProposalStatus::Open,
+        created_at: env.block.time.seconds(),
+        expires_at: env.block.time.seconds() + config.voting_period,
+    };
+    PROPOSALS.save(deps.storage, id, &proposal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_proposal")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("proposer", info.sender)
+        .add_attribute("expires_at", proposal.expires_at.to_string()))
+}
+
+fn load_open_proposal(storage: &dyn cosmwasm_std::Storage, env: &Env, proposal_id: u64) -> Result<Proposal, ContractError> {
+    let proposal = PROPOSALS.may_load(storage, proposal_id)?
+        .ok_or(ContractError::CustomError { message: "Proposal not found".to_string() })?;
+    if proposal.status != ProposalStatus::Open {
+        return Err(ContractError::CustomError { message: "Proposal is not open".to_string() });
+    }
+    if env.block.time.seconds() >= proposal.expires_at {
+        return Err(ContractError::CustomError { message: "Proposal expired".to_string() });
+    }
+    Ok(proposal)
+}
+
+// Performs an approved (or unguarded) action and returns the hook messages it produced
+fn apply_proposal_action(
+    deps: DepsMut,
+    env: &Env,
+    proposer: &Addr,
+    action: &ProposalAction,
+) -> Result<Vec<SubMsg>, ContractError> {
+    match action {
+        ProposalAction::GrantRole { user, role, expires_at } => {
+            // State may have changed while the proposal was open
+            ensure_grantable(deps.storage, role)?;
+            validate_expiry(env, role, *expires_at)?;
+            let user_addr = deps.api.addr_validate(user)?;
+            consume_fan_allowance(deps.storage, env, proposer, role)?;
+            let grant = RoleGrant {
+                granted_at: env.block.time.seconds(),
+                granted_by: proposer.clone(),
+                expires_at: *expires_at,
+            };
+            save_role(deps.storage, &user_addr, role, &grant)?;
+            record_audit(deps.storage, env, "grant_role", proposer, &user_addr, role, None)?;
+            Ok(role_changed_msgs(deps.storage, &user_addr, role, None, true)?)
+        }
+        ProposalAction::RevokeRole { user, role } => {
+            let user_addr = deps.api.addr_validate(user)?;
+            remove_role(deps.storage, &user_addr, role)?;
+            record_audit(deps.storage, env, "revoke_role", proposer, &user_addr, role, None)?;
+            Ok(role_changed_msgs(deps.storage, &user_addr, role, None, false)?)
+        }
+        ProposalAction::ExtendRole { user, role, expires_at } => {
+            // Update the expiry of the existing grant
+            validate_expiry(env, role, *expires_at)?;
+            let user_addr = deps.api.addr_validate(user)?;
+            let mut grant = ROLES.may_load(deps.storage, (user_addr.as_str(), role))?
+                .ok_or(ContractError::CustomError { message: "Role not granted".to_string() })?;
+            let revived = !grant.is_active(env.block.time.seconds());
+            grant.expires_at = *expires_at;
+            save_role(deps.storage, &user_addr, role, &grant)?;
+            if role == FAN_ASSIGNER_ROLE {
+                if let Some(mut allowance) = FAN_ASSIGNER_ALLOWANCES.may_load(deps.storage, user_addr.as_str())? {
+                    allowance.expires_at = *expires_at;
+                    FAN_ASSIGNER_ALLOWANCES.save(deps.storage, user_addr.as_str(), &allowance)?;
+                }
+            }
+            record_audit(deps.storage, env, "extend_role", proposer, &user_addr, role, None)?;
+            // Subscribers treated the lapsed grant as gone, so a revival is announced like a grant
+            if revived {
+                return Ok(role_changed_msgs(deps.storage, &user_addr, role, None, true)?);
+            }
+            Ok(vec![])
+        }
+        ProposalAction::GrantScopedRole { user, role, scope, expires_at } => {
+            ensure_grantable(deps.storage, role)?;
+            validate_expiry(env, role, *expires_at)?;
+            let user_addr = deps.api.addr_validate(user)?;
+            consume_fan_allowance(deps.storage, env, proposer, role)?;
+            let grant = RoleGrant {
+                granted_at: env.block.time.seconds(),
+                granted_by: proposer.clone(),
+                expires_at: *expires_at,
+            };
+            SCOPED_ROLES.save(deps.storage, (user_addr.as_str(), role, scope), &grant)?;
+            record_audit(deps.storage, env, "grant_scoped_role", proposer, &user_addr, role, Some(scope))?;
+            Ok(role_changed_msgs(deps.storage, &user_addr, role, Some(scope), true)?)
+        }
+        ProposalAction::RevokeScopedRole { user, role, scope } => {
+            let user_addr = deps.api.addr_validate(user)?;
+            let key = (user_addr.as_str(), role.as_str(), scope.as_str());
+            if !SCOPED_ROLES.has(deps.storage, key) {
+                return Err(ContractError::CustomError { message: "Scoped role not granted".to_string() });
+            }
+            SCOPED_ROLES.remove(deps.storage, key);
+            record_audit(deps.storage, env, "revoke_scoped_role", proposer, &user_addr, role, Some(scope))?;
+            Ok(role_changed_msgs(deps.storage, &user_addr, role, Some(scope), false)?)
+        }
+        ProposalAction::UpdateSecurity { sensitive_roles, approval_threshold, voting_period } => {
+            validate_security(deps.storage, sensitive_roles, *approval_threshold, *voting_period)?;
+            let mut config = CONFIG.load(deps.storage)?;
+            config.sensitive_roles = sensitive_roles.clone();
+            config.approval_threshold = *approval_threshold;
+            config.voting_period = *voting_period;
+            CONFIG.save(deps.storage, &config)?;
+            // A handover offered before threshold mode was never approved
+            if *approval_threshold > 0 {
+                PENDING_ADMIN.remove(deps.storage);
+            }
+            Ok(vec![])
+        }
+        ProposalAction::ProposeAdmin { new_admin } => {
+            let new_admin_addr = deps.api.addr_validate(new_admin)?;
+            // Replaces any earlier proposal
+            let pending = PendingAdmin {
+                proposer: proposer.clone(),
+                new_admin: new_admin_addr,
+                proposed_at: env.block.time.seconds(),
+            };
+            PENDING_ADMIN.save(deps.storage, &pending)?;
+            Ok(vec![])
+        }
+        ProposalAction::SetRoleAdmin { role, admin_role } => {
+            validate_role_admin(deps.storage, role, admin_role)?;
+            ROLE_ADMIN.save(deps.storage, role, admin_role)?;
+            // Rewiring has no target user; the entry is filed under the role whose admin changed
+            record_audit(deps.storage, env, "set_role_admin", proposer, &env.contract.address, role, None)?;
+            Ok(vec![])
+        }
+        ProposalAction::RegisterRole { role, description, admin_role } => {
+            validate_new_role(deps.storage, role, admin_role)?;
+            let role_info = RoleInfo {
+                description: description.clone(),
+                deprecated: false,
+                registered_at: env.block.time.seconds(),
+            };
+            ROLE_REGISTRY.save(deps.storage, role, &role_info)?;
+            ROLE_ADMIN.save(deps.storage, role, admin_role)?;
+            Ok(vec![])
+        }
+        ProposalAction::SetPermission { action, roles } => {
+            validate_permission(deps.storage, action, roles)?;
+            PERMISSIONS.save(deps.storage, action, roles)?;
+            Ok(vec![])
+        }
+    }
+}
+
+// Whether admin-only configuration changes must go through a proposal
+fn threshold_enabled(storage: &dyn cosmwasm_std::Storage) -> StdResult<bool> {
+    Ok(CONFIG.load(storage)?.approval_threshold > 0)
+}
+
+fn validate_role_admin(storage: &dyn cosmwasm_std::Storage, role: &str, admin_role: &str) -> Result<(), ContractError> {
+    if role == DEFAULT_ADMIN_ROLE {
+        return Err(ContractError::CustomError { message: "Cannot change the admin of DEFAULT_ADMIN_ROLE".to_string() });
+    }
+    ensure_registered(storage, role)?;
+    ensure_registered(storage, admin_role)?;
+    Ok(())
+}
+
+fn validate_new_role(storage: &dyn cosmwasm_std::Storage, role: &str, admin_role: &str) -> Result<(), ContractError> {
+    if !is_valid_role_name(role) {
+        return Err(ContractError::CustomError { message: "Role names must be non-empty A-Z, 0-9 and _".to_string() });
+    }
+    if ROLE_REGISTRY.has(storage, role) {
+        return Err(ContractError::CustomError { message: "Role already registered".to_string() });
+    }
+    // A role may administer itself, otherwise its admin must already be known
+    if admin_role != role {
+        ensure_registered(storage, admin_role)?;
+    }
+    Ok(())
+}
+
+fn validate_permission(storage: &dyn cosmwasm_std::Storage, action: &str, roles: &[String]) -> Result<(), ContractError> {
+    if action.is_empty() || roles.is_empty() {
+        return Err(ContractError::CustomError { message: "Permission needs an action and at least one role".to_string() });
+    }
+    for role in roles {
+        ensure_registered(storage, role)?;
+    }
+    Ok(())
+}
+
+fn validate_security(
+    storage: &dyn cosmwasm_std::Storage,
+    sensitive_roles: &[String],
+    approval_threshold: u32,
+    voting_period: u64,
+) -> Result<(), ContractError> {
+    for role in sensitive_roles {
+        ensure_registered(storage, role)?;
+    }
+    if voting_period == 0 {
+        return Err(ContractError::CustomError { message: "Voting period must be positive".to_string() });
+    }
+
+    // A threshold above the admin count could never be met
+    let admin_count = ROLE_MEMBER_COUNT.may_load(storage, DEFAULT_ADMIN_ROLE)?.unwrap_or(0);
+    if u64::from(approval_threshold) > admin_count {
+        return Err(ContractError::CustomError { message: format!("Approval threshold exceeds admin count of {}", admin_count) });
+    }
+    Ok(())
+}
+
+fn validate_batch_size(storage: &dyn cosmwasm_std::Storage, size: usize) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    if size == 0 {
+        return Err(ContractError::CustomError { message: "Empty batch".to_string() });
+    }
+    if size > config.max_batch_size as usize {
+        return Err(ContractError::CustomError { message: format!("Batch size exceeds limit of {}", config.max_batch_size) });
+    }
+    Ok(())
+}
+
+fn validate_pause_scope(scope: &str) -> Result<(), ContractError> {
+    if !PAUSE_SCOPES.contains(&scope) {
+        return Err(ContractError::CustomError { message: format!("Unknown pause scope: {}", scope) });
+    }
+    Ok(())
+}
+
+#[entry_point]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::HasRole { user, role } => to_json_binary(&query_has_role(deps, env, user, role)?),
+        QueryMsg::HasAnyRole { user, roles } => to_json_binary(&has_any_role(deps, env, user, roles)?),
+        QueryMsg::HasScopedRole { user, role, scope } => {
+            let user_addr = deps.api.addr_validate(&user)?;
+            to_json_binary(&BoolResponse { result: has_scoped_role(deps.storage, &env, &user_addr, &role, &scope)? })
+        }
+        QueryMsg::CanPerform { user, action, scope } => {
+            let user_addr = deps.api.addr_validate(&user)?;
+            to_json_binary(&BoolResponse { result: can_perform(deps.storage, &env, &user_addr, &action, scope.as_deref())? })
+        }
+        QueryMsg::IsBlocked { user } => {
+            let user_addr = deps.api.addr_validate(&user)?;
+            to_json_binary(&BoolResponse { result: is_blocked(deps.storage, &env, &user_addr)? })
+        }
+        QueryMsg::IsSessionAuthorized { owner, session, action } => {
+            let owner_addr = deps.api.addr_validate(&owner)?;
+            let session_addr = deps.api.addr_validate(&session)?;
+            to_json_binary(&BoolResponse { result: is_session_authorized(deps.storage, &env, &owner_addr, &session_addr, &action)? })
+        }
+        QueryMsg::ListSessionKeys { owner, start_after, limit } => to_json_binary(&list_session_keys(deps, owner, start_after, limit)?),
+        QueryMsg::NftInfo { token_id } => to_json_binary(&badge_nft_info(deps, env, token_id)?),
+        QueryMsg::OwnerOf { token_id, .. } => to_json_binary(&badge_owner_of(deps, env, token_id)?),
+        QueryMsg::Tokens { owner, start_after, limit } => to_json_binary(&badge_tokens(deps, env, owner, start_after, limit)?),
+        QueryMsg::GetApplication { applicant, role } => {
+            let applicant_addr = deps.api.addr_validate(&applicant)?;
+            let application = APPLICATIONS.may_load(deps.storage, (role.as_str(), applicant_addr.as_str()))?;
+            to_json_binary(&ApplicationResponse { application })
+        }
+        QueryMsg::ListApplications { role, status, start_after, limit } => to_json_binary(&list_applications(deps, role, status, start_after, limit)?),
+        QueryMsg::GetBlockEntry { user } => {
+            let user_addr = deps.api.addr_validate(&user)?;
+            let entry = BLOCKLIST.may_load(deps.storage, user_addr.as_str())?;
+            let active = entry.as_ref().is_some_and(|entry| entry.is_active(env.block.time.seconds()));
+            to_json_binary(&BlockEntryResponse { entry, active })
+        }
+        QueryMsg::ListPermissions { start_after, limit } => to_json_binary(&list_permissions(deps, start_after, limit)?),
+        QueryMsg::HasAllRoles { user, roles } => to_json_binary(&has_all_roles(deps, env, user, roles)?),
+        QueryMsg::GetUserRoles { user } => to_json_binary(&get_user_roles(deps, env, user)?),
+        QueryMsg::GetRoleAdmin { role } => to_json_binary(&query_role_admin(deps, role)?),
+        QueryMsg::GetRoleGrant { user, role } => to_json_binary(&get_role_grant(deps, env, user, role)?),
+        QueryMsg::ListRoleMembers { role, start_after, limit } => to_json_binary(&list_role_members(deps, env, role, start_after, limit)?),
+        QueryMsg::CountRoleMembers { role } => to_json_binary(&count_role_members(deps, env, role)?),
+        QueryMsg::ListRoles {} => to_json_binary(&list_roles(deps)?),
+        QueryMsg::ListRegisteredRoles { start_after, limit } => to_json_binary(&list_registered_roles(deps, start_after, limit)?),
+        QueryMsg::IsPaused { scope } => to_json_binary(&is_paused(deps, scope)?),
+        QueryMsg::GetFanAssignerAllowance { assigner } => to_json_binary(&get_fan_assigner_allowance(deps, env, assigner)?),
+        QueryMsg::GetProposal { proposal_id } => to_json_binary(&PROPOSALS.load(deps.storage, proposal_id)?),
+        QueryMsg::ListProposals { start_after, limit } => to_json_binary(&list_proposals(deps, start_after, limit)?),
+        QueryMsg::AuditLog { user, role, start_after, limit } => to_json_binary(&query_audit_log(deps, user, role, start_after, limit)?),
+        QueryMsg::ListVoucherSigners {} => to_json_binary(&list_voucher_signers(deps)?),
+        QueryMsg::GetVoucherClaims { signer, nonce } => to_json_binary(&VoucherClaimsResponse {
+            claims: VOUCHER_CLAIM_COUNT.may_load(deps.storage, (&signer, nonce))?.unwrap_or(0),
+        }),
+        QueryMsg::ListHooks {} => to_json_binary(&list_hooks(deps)?),
+        QueryMsg::PlatformStatus {} => to_json_binary(&platform_status(deps)?),
+        QueryMsg::GetConfig {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::GetPendingAdmin {} => to_json_binary(&PendingAdminResponse { pending: PENDING_ADMIN.may_load(deps.storage)? }),
+    }
+}
+
+fn query_has_role(deps: Deps, env: Env, user: String, role: String) -> StdResult<BoolResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let result = has_role(deps.storage, &env, &user_addr, &role)?;
+    Ok(BoolResponse { result })
+}
+
+fn has_any_role(deps: Deps, env: Env, user: String, roles: Vec<String>) -> StdResult<BoolResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    
+    for role in roles {
+        if has_role(deps.storage, &env, &user_addr, &role)? {
+            return Ok(BoolResponse { result: true });
+        }
+    }
+    
+    Ok(BoolResponse { result: false })
+}
+
+fn has_all_roles(deps: Deps, env: Env, user: String, roles: Vec<String>) -> StdResult<BoolResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    
+    for role in roles {
+        if !has_role(deps.storage, &env, &user_addr, &role)? {
+            return Ok(BoolResponse { result: false });
+        }
+    }
+    
+    Ok(BoolResponse { result: true })
+}
+
+fn get_user_roles(deps: Deps, env: Env, user: String) -> StdResult<RolesResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let now = env.block.time.seconds();
+    
+    // Expired grants are left in storage but are not reported
+    let roles = ROLES
+        .prefix(user_addr.as_str())
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((role, grant)) if grant.is_active(now) => Some(Ok(role)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        })
+        .collect::<StdResult<Vec<String>>>()?;
+    
+    Ok(RolesResponse { roles })
+}
+
+fn list_role_members(
+    deps: Deps,
+    env: Env,
+    role: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RoleMembersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let now = env.block.time.seconds();
+    
+    // At most `limit` grants are read per page; expired ones are skipped, so a page can come back short
+    let scanned = ROLE_MEMBERS
+        .prefix(&role)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+    let next_start_after = (scanned.len() == limit).then(|| scanned.last().cloned()).flatten();
+    
+    let mut members = vec![];
+    for user in scanned {
+        if ROLES.may_load(deps.storage, (&user, &role))?.is_some_and(|grant| grant.is_active(now)) {
+            members.push(user);
+        }
+    }
+    
+    Ok(RoleMembersResponse { members, next_start_after })
+}
+
+// Counts active grants; lapsed ones are subtracted until ClearExpiredRole removes them
+fn count_role_members(deps: Deps, env: Env, role: String) -> StdResult<RoleMemberCountResponse> {
+    let stored = ROLE_MEMBER_COUNT.may_load(deps.storage, &role)?.unwrap_or(0);
+    let now = env.block.time.seconds();
+    let expired = ROLE_EXPIRIES
+        .sub_prefix(&role)
+        .keys(deps.storage, None, Some(Bound::exclusive((now + 1, ""))), Order::Ascending)
+        .count() as u64;
+    Ok(RoleMemberCountResponse { count: stored.saturating_sub(expired) })
+}
+
+// Lists every role currently held by at least one address
+fn list_roles(deps: Deps) -> StdResult<RolesResponse> {
+    let roles = ROLE_MEMBER_COUNT
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<String>>>()?;
+    
+    Ok(RolesResponse { roles })
+}
+
+fn list_registered_roles(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<RegisteredRolesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+    
+    let roles = ROLE_REGISTRY
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (role, role_info) = item?;
+            let admin_role = get_role_admin(deps.storage, &role)?;
+            Ok(RegisteredRole {
+                role,
+                description: role_info.description,
+                admin_role,
+                deprecated: role_info.deprecated,
+            })
+        })
+        .collect::<StdResult<Vec<RegisteredRole>>>()?;
+    
+    Ok(RegisteredRolesResponse { roles })
+}
+
+// A scope counts as paused while the whole contract is paused
+fn is_paused(deps: Deps, scope: Option<String>) -> StdResult<BoolResponse> {
+    let mut result = PAUSED.may_load(deps.storage)?.unwrap_or(false);
+    if let Some(scope) = scope {
+        result = result || PAUSED_SCOPES.may_load(deps.storage, &scope)?.unwrap_or(false);
+    }
+    Ok(BoolResponse { result })
+}
+
+fn query_role_admin(deps: Deps, role: String) -> StdResult<RoleAdminResponse> {
+    let admin_role = get_role_admin(deps.storage, &role)?;
+    Ok(RoleAdminResponse { role, admin_role })
+}
+
+fn get_role_grant(deps: Deps, env: Env, user: String, role: String) -> StdResult<RoleGrantResponse> {
+    let user_addr = deps.api.addr_validate(&user)?;
+    let grant = ROLES.may_load(deps.storage, (user_addr.as_str(), &role))?;
+    let active = grant.as_ref().is_some_and(|g| g.is_active(env.block.time.seconds()));
+    Ok(RoleGrantResponse { grant, active })
+}
+
+// Helper to check if an address has a role; expired grants count as absent
+pub fn has_role(storage: &dyn cosmwasm_std::Storage, env: &Env, user: &Addr, role: &str) -> StdResult<bool> {
+    Ok(ROLES
+        .may_load(storage, (user.as_str(), role))?
+        .is_some_and(|grant| grant.is_active(env.block.time.seconds())))
+}
+
+// Scoped check that falls back to the global grant, e.g. a global moderator moderates every tribe
+pub fn has_scoped_role(storage: &dyn cosmwasm_std::Storage, env: &Env, user: &Addr, role: &str, scope: &str) -> StdResult<bool> {
+    if has_role(storage, env, user, role)? {
+        return Ok(true);
+    }
+    Ok(SCOPED_ROLES
+        .may_load(storage, (user.as_str(), role, scope))?
+        .is_some_and(|grant| grant.is_active(env.block.time.seconds())))
+}
+
+fn load_pending_application(storage: &dyn cosmwasm_std::Storage, applicant: &Addr, role: &str) -> Result<Application, ContractError> {
+    let application = APPLICATIONS.may_load(storage, (role, applicant.as_str()))?
+        .ok_or(ContractError::CustomError { message: "Application not found".to_string() })?;
+    if application.status != ApplicationStatus::Pending {
+        return Err(ContractError::CustomError { message: "Application is not pending".to_string() });
+    }
+    Ok(application)
+}
+
+pub fn is_blocked(storage: &dyn cosmwasm_std::Storage, env: &Env, user: &Addr) -> StdResult<bool> {
+    Ok(BLOCKLIST
+        .may_load(storage, user.as_str())?
+        .is_some_and(|entry| entry.is_active(env.block.time.seconds())))
+}
+
+// A blocked owner cannot act through a session key either
+pub fn is_session_authorized(storage: &dyn cosmwasm_std::Storage, env: &Env, owner: &Addr, session: &Addr, action: &str) -> StdResult<bool> {
+    if is_blocked(storage, env, owner)? {
+        return Ok(false);
+    }
+    Ok(SESSION_KEYS
+        .may_load(storage, (owner.as_str(), session.as_str()))?
+        .is_some_and(|key| env.block.time.seconds() < key.expires_at && key.actions.iter().any(|allowed| allowed == action)))
+}
+
+// Unknown actions and blocked users are denied
+pub fn can_perform(storage: &dyn cosmwasm_std::Storage, env: &Env, user: &Addr, action: &str, scope: Option<&str>) -> StdResult<bool> {
+    if is_blocked(storage, env, user)? {
+        return Ok(false);
+    }
+
+    let roles = PERMISSIONS.may_load(storage, action)?.unwrap_or_default();
+    for role in roles {
+        let allowed = match scope {
+            Some(scope) => has_scoped_role(storage, env, user, &role, scope)?,
+            None => has_role(storage, env, user, &role)?,
+        };
+        if allowed {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Helper to store a role grant while keeping the role -> users index in sync
+fn save_role(storage: &mut dyn cosmwasm_std::Storage, user: &Addr, role: &str, grant: &RoleGrant) -> StdResult<()> {
+    match ROLES.may_load(storage, (user.as_str(), role))? {
+        Some(existing) => {
+            if let Some(expires_at) = existing.expires_at {
+                ROLE_EXPIRIES.remove(storage, (role, expires_at, user.as_str()));
+            }
+        }
+        None => {
+            let count = ROLE_MEMBER_COUNT.may_load(storage, role)?.unwrap_or(0);
+            ROLE_MEMBER_COUNT.save(storage, role, &(count + 1))?;
+        }
+    }
+    
+    ROLES.save(storage, (user.as_str(), role), grant)?;
+    ROLE_MEMBERS.save(storage, (role, user.as_str()), &true)?;
+    if let Some(expires_at) = grant.expires_at {
+        ROLE_EXPIRIES.save(storage, (role, expires_at, user.as_str()), &true)?;
+    }
+
+    // Mint the soulbound badge on first grant; re-grants and extensions keep the original
+    if BADGE_ROLES.contains(&role) && !BADGES.has(storage, (user.as_str(), role)) {
+        let badge = Badge {
+            role: role.to_string(),
+            issued_at: grant.granted_at,
+        };
+        BADGES.save(storage, (user.as_str(), role), &badge)?;
+    }
+    Ok(())
+}
+
+// Helper to remove a role grant while keeping the role -> users index in sync
+fn remove_role(storage: &mut dyn cosmwasm_std::Storage, user: &Addr, role: &str) -> Result<(), ContractError> {
+    let Some(existing) = ROLES.may_load(storage, (user.as_str(), role))? else {
+        return Ok(());
+    };
+    
+    // Never leave the contract without an admin, or with fewer admins than proposals need
+    if role == DEFAULT_ADMIN_ROLE {
+        let admin_count = ROLE_MEMBER_COUNT.may_load(storage, role)?.unwrap_or(0);
+        if admin_count <= 1 {
+            return Err(ContractError::CustomError { message: "Cannot remove the last admin".to_string() });
+        }
+        let threshold = CONFIG.may_load(storage)?.map_or(0, |config| config.approval_threshold);
+        if admin_count - 1 < u64::from(threshold) {
+            return Err(ContractError::CustomError { message: format!("Cannot drop below the approval threshold of {} admins", threshold) });
+        }
+    }
+    
+    ROLES.remove(storage, (user.as_str(), role));
+    ROLE_MEMBERS.remove(storage, (role, user.as_str()));
+    if role == FAN_ASSIGNER_ROLE {
+        FAN_ASSIGNER_ALLOWANCES.remove(storage, user.as_str());
+    }
+    if let Some(expires_at) = existing.expires_at {
+        ROLE_EXPIRIES.remove(storage, (role, expires_at, user.as_str()));
+    }
+    BADGES.remove(storage, (user.as_str(), role));
+    
+    let count = ROLE_MEMBER_COUNT.may_load(storage, role)?.unwrap_or(0);
+    if count <= 1 {
+        ROLE_MEMBER_COUNT.remove(storage, role);
+    } else {
+        ROLE_MEMBER_COUNT.save(storage, role, &(count - 1))?;
+    }
+    Ok(())
+}
+
+// Helper to count a FAN_ROLE grant against the granter's fan-assigner allowance; only the default
+// admin grants fans without one
+fn consume_fan_allowance(storage: &mut dyn cosmwasm_std::Storage, env: &Env, granter: &Addr, role: &str) -> Result<(), ContractError> {
+    if role != FAN_ROLE || has_role(storage, env, granter, DEFAULT_ADMIN_ROLE)? {
+        return Ok(());
+    }
+    let mut allowance = FAN_ASSIGNER_ALLOWANCES.may_load(storage, granter.as_str())?
+        .ok_or(ContractError::CustomError { message: "No fan assignment allowance".to_string() })?;
+    if allowance.expires_at.is_some_and(|expires_at| env.block.time.seconds() >= expires_at) {
+        return Err(ContractError::CustomError { message: "Fan assignment allowance expired".to_string() });
+    }
+    if allowance.remaining() == Some(0) {
+        return Err(ContractError::CustomError { message: "Fan assignment allowance exhausted".to_string() });
+    }
+    allowance.assigned_count += 1;
+    FAN_ASSIGNER_ALLOWANCES.save(storage, granter.as_str(), &allowance)?;
+    Ok(())
+}
+
+// FAN_ASSIGNER_ROLE always comes with an allowance, so only AuthorizeFanAssigner grants it
+fn ensure_not_fan_assigner(role: &str) -> Result<(), ContractError> {
+    if role == FAN_ASSIGNER_ROLE {
+        return Err(ContractError::CustomError { message: "FAN_ASSIGNER_ROLE is granted with AuthorizeFanAssigner".to_string() });
+    }
+    Ok(())
+}
+
+// Allowance of a fan assigner and whether it can still assign fans
+pub fn get_fan_assigner_allowance(deps: Deps, env: Env, assigner: String) -> StdResult<FanAssignerAllowanceResponse> {
+    let assigner_addr = deps.api.addr_validate(&assigner)?;
+    let allowance = FAN_ASSIGNER_ALLOWANCES.may_load(deps.storage, assigner_addr.as_str())?;
+    let remaining = allowance.as_ref().and_then(|allowance| allowance.remaining());
+    let unexpired = allowance.as_ref().is_some_and(|allowance| allowance.expires_at.is_none_or(|expires_at| env.block.time.seconds() < expires_at));
+    let active = has_role(deps.storage, &env, &assigner_addr, FAN_ASSIGNER_ROLE)? && unexpired && remaining != Some(0);
+    Ok(FanAssignerAllowanceResponse { allowance, remaining, active })
+}
+
+fn list_proposals(deps: Deps, start_after: Option<u64>, limit: Option<u32>) -> StdResult<ProposalsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let proposals = PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, proposal)| proposal))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ProposalsResponse { proposals })
+}
+
+fn query_audit_log(
+    deps: Deps,
+    user: Option<String>,
+    role: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<AuditLogResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let log = audit_log();
+
+    let user = user.map(|user| deps.api.addr_validate(&user)).transpose()?;
+    let entries = match (user, role) {
+        (Some(user), Some(role)) => log.idx.target_role.prefix((user.to_string(), role)).range(deps.storage, start, None, Order::Ascending),
+        (Some(user), None) => log.idx.target.prefix(user.to_string()).range(deps.storage, start, None, Order::Ascending),
+        (None, Some(role)) => log.idx.role.prefix(role).range(deps.storage, start, None, Order::Ascending),
+        (None, None) => log.range(deps.storage, start, None, Order::Ascending),
+    }
+    .take(limit)
+    .map(|item| item.map(|(_, entry)| entry))
+    .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(AuditLogResponse { entries })
+}
+
+fn list_voucher_signers(deps: Deps) -> StdResult<VoucherSignersResponse> {
+    let signers = VOUCHER_SIGNERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(name, signer)| VoucherSignerInfo { name, signer }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(VoucherSignersResponse { signers })
+}
+
+fn list_session_keys(deps: Deps, owner: String, start_after: Option<String>, limit: Option<u32>) -> StdResult<SessionKeysResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let session_keys = SESSION_KEYS
+        .prefix(owner_addr.as_str())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, key)| key))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(SessionKeysResponse { session_keys })
+}
+
+pub fn badge_token_id(owner: &Addr, role: &str) -> String {
+    format!("{}:{}", role, owner)
+}
+
+// Badges of lapsed grants stay stored until the role is cleared, but are not reported as live
+fn load_badge(deps: Deps, env: &Env, token_id: &str) -> StdResult<(Addr, Badge)> {
+    let (role, owner) = token_id
+        .split_once(':')
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("Invalid token id"))?;
+    let badge = BADGES.load(deps.storage, (owner, role))?;
+    let owner = Addr::unchecked(owner);
+    if !has_role(deps.storage, env, &owner, role)? {
+        return Err(cosmwasm_std::StdError::not_found("Badge"));
+    }
+    Ok((owner, badge))
+}
+
+fn badge_nft_info(deps: Deps, env: Env, token_id: String) -> StdResult<NftInfoResponse> {
+    let (_, badge) = load_badge(deps, &env, &token_id)?;
+    let token_uri = BADGE_URIS.may_load(deps.storage, &badge.role)?;
+    Ok(NftInfoResponse { token_uri, extension: badge })
+}
+
+fn badge_owner_of(deps: Deps, env: Env, token_id: String) -> StdResult<OwnerOfResponse> {
+    let (owner, _) = load_badge(deps, &env, &token_id)?;
+    Ok(OwnerOfResponse { owner: owner.to_string(), approvals: vec![] })
+}
+
+fn badge_tokens(deps: Deps, env: Env, owner: String, start_after: Option<String>, limit: Option<u32>) -> StdResult<TokensResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // `start_after` is a token id; its role part is the key within the owner's badges
+    let start_role = start_after.as_deref().map(|token_id| token_id.split_once(':').map_or(token_id, |(role, _)| role));
+    let start = start_role.map(Bound::exclusive);
+
+    let tokens = BADGES
+        .prefix(owner_addr.as_str())
+        .keys(deps.storage, start, None, Order::Ascending)
+        .filter(|role| match role {
+            Ok(role) => has_role(deps.storage, &env, &owner_addr, role).unwrap_or(false),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|role| role.map(|role| badge_token_id(&owner_addr, &role)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TokensResponse { tokens })
+}
+
+fn list_applications(
+    deps: Deps,
+    role: String,
+    status: Option<ApplicationStatus>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ApplicationsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let applications = APPLICATIONS
+        .prefix(&role)
+        .range(deps.storage, start, None, Order::Ascending)
+        .map(|item| item.map(|(_, application)| application))
+        .filter(|item| match (item, &status) {
+            (Ok(application), Some(status)) => application.status == *status,
+            _ => true,
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ApplicationsResponse { applications })
+}
+
+fn list_permissions(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<PermissionsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_deref().map(Bound::exclusive);
+
+    let permissions = PERMISSIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(action, roles)| Permission { action, roles }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(PermissionsResponse { permissions })
+}
+
+fn platform_status(deps: Deps) -> StdResult<PlatformStatusResponse> {
+    let contracts = PLATFORM_CONTRACTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (contract, platform_contract) = item?;
+            let paused = deps
+                .querier
+                .query_wasm_smart::<BoolResponse>(contract.clone(), &PlatformQuery::IsPaused {})
+                .ok()
+                .map(|response| response.result);
+            Ok(PlatformContractStatus {
+                contract,
+                name: platform_contract.name,
+                kind: platform_contract.kind,
+                paused,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PlatformStatusResponse {
+        role_manager_paused: PAUSED.may_load(deps.storage)?.unwrap_or(false),
+        contracts,
+    })
+}
+
+fn list_hooks(deps: Deps) -> StdResult<HooksResponse> {
+    let hooks = HOOKS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(HooksResponse { hooks })
+}
+
+// Helper to get the role whose holders may grant and revoke `role`
+pub fn get_role_admin(storage: &dyn cosmwasm_std::Storage, role: &str) -> StdResult<String> {
+    Ok(ROLE_ADMIN.may_load(storage, role)?.unwrap_or_else(|| DEFAULT_ADMIN_ROLE.to_string()))
+}
+
+// Helper to ensure the sender holds the admin role of `role`
+fn only_role_admin(storage: &dyn cosmwasm_std::Storage, env: &Env, sender: &Addr, role: &str) -> Result<(), ContractError> {
+    let admin_role = get_role_admin(storage, role)?;
+    if !has_role(storage, env, sender, &admin_role)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+// Helper to ensure a role is part of the registry
+fn ensure_registered(storage: &dyn cosmwasm_std::Storage, role: &str) -> Result<RoleInfo, ContractError> {
+    ROLE_REGISTRY.may_load(storage, role)?
+        .ok_or(ContractError::CustomError { message: format!("Role not registered: {}", role) })
+}
+
+// Helper to ensure a role is registered and has not been deprecated
+fn ensure_grantable(storage: &dyn cosmwasm_std::Storage, role: &str) -> Result<(), ContractError> {
+    if ensure_registered(storage, role)?.deprecated {
+        return Err(ContractError::CustomError { message: format!("Role is deprecated: {}", role) });
+    }
+    Ok(())
+}
+
+fn is_valid_role_name(role: &str) -> bool {
+    !role.is_empty() && role.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+// Helper to reject expiry times that are not in the future; admin grants never expire
+fn validate_expiry(env: &Env, role: &str, expires_at: Option<u64>) -> Result<(), ContractError> {
+    if let Some(expires_at) = expires_at {
+        if role == DEFAULT_ADMIN_ROLE {
+            return Err(ContractError::CustomError { message: "DEFAULT_ADMIN_ROLE cannot expire".to_string() });
+        }
+        if expires_at <= env.block.time.seconds() {
+            return Err(ContractError::CustomError { message: "Expiry must be in the future".to_string() });
+        }
+    }
+    Ok(())
+}
+
+fn format_expiry(expires_at: Option<u64>) -> String {
+    expires_at.map_or_else(|| "never".to_string(), |t| t.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile_nft_minter;
+    use cosmwasm_std::{from_json, CosmosMsg};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    #[test]
+    fn proper_initialization() {
+        let mut deps = mock_dependencies();
+        let info = mock_info("creator", &[]);
+        
+        // Instantiate the contract
+        let res = instantiate(deps.as_mut(), mock_env(), info, InstantiateMsg {}).unwrap();
+        assert_eq!(0, res.messages.len());
+        
+        // Verify creator has admin role
+        let has_role = query_has_role(
+            deps.as_ref(),
+            mock_env(),
+            "creator".to_string(),
+            DEFAULT_ADMIN_ROLE.to_string(),
+        )
+        .unwrap();
+        assert_eq!(true, has_role.result);
+    }
+
+    #[test]
+    fn test_grant_role() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        
+        // Instantiate contract with admin
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        
+        // Grant ARTIST_ROLE to user
+        let res = grantRole(
+            deps.as_mut(),
+            mock_env(),
+            admin_info,
+            "user".to_string(),
+            ARTIST_ROLE.to_string(),
+            None,
+        )
+        .unwrap();
+        
+        assert_eq!("grant_role", res.attributes[0].value);
+        
+        // Verify user has ARTIST_ROLE
         let has_role = query_has_role(
             deps.as_ref(),
+            mock_env(),
             "user".to_string(),
             ARTIST_ROLE.to_string(),
         )
         .unwrap();
         assert_eq!(true, has_role.result);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_role_admin_hierarchy() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        // FAN_ROLE is administered by fan assigners out of the box
+        let res = query_role_admin(deps.as_ref(), FAN_ROLE.to_string()).unwrap();
+        assert_eq!(FAN_ASSIGNER_ROLE, res.admin_role);
+
+        // Hand ARTIST_ROLE over to organizers
+        setRoleAdmin(deps.as_mut(), mock_env(), admin_info.clone(), ARTIST_ROLE.to_string(), ORGANIZER_ROLE.to_string()).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "organizer".to_string(), ORGANIZER_ROLE.to_string(), None).unwrap();
+
+        // The default admin no longer administers ARTIST_ROLE
+        let err = grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "artist".to_string(), ARTIST_ROLE.to_string(), None).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+
+        // Organizers can grant and revoke it
+        let organizer_info = mock_info("organizer", &[]);
+        grantRole(deps.as_mut(), mock_env(), organizer_info.clone(), "artist".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        assert!(query_has_role(deps.as_ref(), mock_env(), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap().result);
+        revokeRole(deps.as_mut(), mock_env(), organizer_info.clone(), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap();
+        assert!(!query_has_role(deps.as_ref(), mock_env(), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap().result);
+
+        // Only the default admin can re-assign role admins
+        let err = setRoleAdmin(deps.as_mut(), mock_env(), organizer_info, ARTIST_ROLE.to_string(), ORGANIZER_ROLE.to_string()).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+    }
+
+    #[test]
+    fn test_role_enumeration() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        // Custom roles show up alongside the built-in ones
+        registerRole(deps.as_mut(), mock_env(), admin_info.clone(), "CURATOR_ROLE".to_string(), "Playlist curator".to_string(), DEFAULT_ADMIN_ROLE.to_string()).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), "CURATOR_ROLE".to_string(), None).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "bob".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "carol".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+
+        let roles = get_user_roles(deps.as_ref(), mock_env(), "alice".to_string()).unwrap();
+        assert_eq!(vec![ARTIST_ROLE.to_string(), "CURATOR_ROLE".to_string()], roles.roles);
+
+        let roles = list_roles(deps.as_ref()).unwrap();
+        assert_eq!(
+            vec![ARTIST_ROLE.to_string(), "CURATOR_ROLE".to_string(), DEFAULT_ADMIN_ROLE.to_string()],
+            roles.roles
+        );
+
+        // Members are paginated in address order
+        let page = list_role_members(deps.as_ref(), mock_env(), ARTIST_ROLE.to_string(), None, Some(2)).unwrap();
+        assert_eq!(vec!["alice".to_string(), "bob".to_string()], page.members);
+        assert_eq!(Some("bob".to_string()), page.next_start_after);
+        let page = list_role_members(deps.as_ref(), mock_env(), ARTIST_ROLE.to_string(), Some("bob".to_string()), Some(2)).unwrap();
+        assert_eq!(vec!["carol".to_string()], page.members);
+        assert_eq!(None, page.next_start_after);
+
+        // Revoking keeps the index and count in sync, granting twice does not double count
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "bob".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        revokeRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), "CURATOR_ROLE".to_string()).unwrap();
+        assert_eq!(3, count_role_members(deps.as_ref(), mock_env(), ARTIST_ROLE.to_string()).unwrap().count);
+        assert_eq!(0, count_role_members(deps.as_ref(), mock_env(), "CURATOR_ROLE".to_string()).unwrap().count);
+        assert!(!list_roles(deps.as_ref()).unwrap().roles.contains(&"CURATOR_ROLE".to_string()));
+    }
+
+    #[test]
+    fn test_role_expiry() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        let env = mock_env();
+        let now = env.block.time.seconds();
+        instantiate(deps.as_mut(), env.clone(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        // Expiry has to be in the future
+        let err = grantRole(deps.as_mut(), env.clone(), admin_info.clone(), "partner".to_string(), BRAND_ROLE.to_string(), Some(now)).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Expiry must be in the future".to_string() }, err);
+
+        grantRole(deps.as_mut(), env.clone(), admin_info.clone(), "partner".to_string(), BRAND_ROLE.to_string(), Some(now + 100)).unwrap();
+        let grant = get_role_grant(deps.as_ref(), env.clone(), "partner".to_string(), BRAND_ROLE.to_string()).unwrap();
+        assert!(grant.active);
+        assert_eq!(Some(RoleGrant { granted_at: now, granted_by: Addr::unchecked("admin"), expires_at: Some(now + 100) }), grant.grant);
+
+        // Once the campaign ends the grant no longer counts
+        let mut later = env.clone();
+        later.block.time = later.block.time.plus_seconds(100);
+        assert!(!has_role(deps.as_ref().storage, &later, &Addr::unchecked("partner"), BRAND_ROLE).unwrap());
+        assert!(get_user_roles(deps.as_ref(), later.clone(), "partner".to_string()).unwrap().roles.is_empty());
+        assert!(list_role_members(deps.as_ref(), later.clone(), BRAND_ROLE.to_string(), None, None).unwrap().members.is_empty());
+        assert_eq!(1, count_role_members(deps.as_ref(), env.clone(), BRAND_ROLE.to_string()).unwrap().count);
+        assert_eq!(0, count_role_members(deps.as_ref(), later.clone(), BRAND_ROLE.to_string()).unwrap().count);
+
+        // Lapsed grants still use up a page's scan budget, and the cursor moves past them
+        for user in ["brand_a", "brand_b"] {
+            grantRole(deps.as_mut(), env.clone(), admin_info.clone(), user.to_string(), BRAND_ROLE.to_string(), Some(now + 100)).unwrap();
+        }
+        grantRole(deps.as_mut(), env.clone(), admin_info.clone(), "brand_c".to_string(), BRAND_ROLE.to_string(), None).unwrap();
+        let page = list_role_members(deps.as_ref(), later.clone(), BRAND_ROLE.to_string(), None, Some(2)).unwrap();
+        assert!(page.members.is_empty());
+        let page = list_role_members(deps.as_ref(), later.clone(), BRAND_ROLE.to_string(), page.next_start_after, Some(2)).unwrap();
+        assert_eq!(vec!["brand_c".to_string()], page.members);
+        assert_eq!(1, count_role_members(deps.as_ref(), later.clone(), BRAND_ROLE.to_string()).unwrap().count);
+
+        // Extending the grant revives it
+        extendRole(deps.as_mut(), later.clone(), admin_info, "partner".to_string(), BRAND_ROLE.to_string(), None).unwrap();
+        assert!(has_role(deps.as_ref().storage, &later, &Addr::unchecked("partner"), BRAND_ROLE).unwrap());
+        assert_eq!(2, count_role_members(deps.as_ref(), later.clone(), BRAND_ROLE.to_string()).unwrap().count);
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        // Storage as written by the version without grant expiries
+        STORED_ROLES.save(deps.as_mut().storage, ("admin", DEFAULT_ADMIN_ROLE), &StoredRoleGrant::Legacy(true)).unwrap();
+        STORED_ROLES.save(deps.as_mut().storage, ("artist", ARTIST_ROLE), &StoredRoleGrant::Legacy(true)).unwrap();
+        STORED_ROLES.save(deps.as_mut().storage, ("curator", "CURATOR_ROLE"), &StoredRoleGrant::Legacy(true)).unwrap();
+        ROLES.load(deps.as_ref().storage, ("artist", ARTIST_ROLE)).unwrap_err();
+
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert_eq!("3", res.attributes[1].value);
+        let grant = get_role_grant(deps.as_ref(), env.clone(), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap();
+        assert!(grant.active);
+        assert_eq!(Some(RoleGrant { granted_at: env.block.time.seconds(), granted_by: env.contract.address.clone(), expires_at: None }), grant.grant);
+        assert_eq!(1, count_role_members(deps.as_ref(), mock_env(), DEFAULT_ADMIN_ROLE.to_string()).unwrap().count);
+
+        // Built-in roles are seeded and custom roles already in use are registered
+        assert_eq!("1", res.attributes[2].value);
+        assert_eq!(9, list_registered_roles(deps.as_ref(), None, Some(30)).unwrap().roles.len());
+        assert_eq!(FAN_ASSIGNER_ROLE, query_role_admin(deps.as_ref(), FAN_ROLE.to_string()).unwrap().admin_role);
+        assert_eq!(8, list_permissions(deps.as_ref(), None, None).unwrap().permissions.len());
+
+        // The missing config gets the defaults, so grants work again
+        assert_eq!(DEFAULT_MAX_BATCH_SIZE, CONFIG.load(deps.as_ref().storage).unwrap().max_batch_size);
+        grantRole(deps.as_mut(), env.clone(), mock_info("admin", &[]), "bob".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+
+        // Running it again leaves converted grants alone
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert_eq!("0", res.attributes[1].value);
+        assert_eq!("0", res.attributes[2].value);
+        assert_eq!(2, count_role_members(deps.as_ref(), mock_env(), ARTIST_ROLE.to_string()).unwrap().count);
+    }
+
+    #[test]
+    fn test_role_registry() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        // Typos are rejected instead of silently creating a role
+        let err = grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "user".to_string(), "ARTIST_ROLLE".to_string(), None).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Role not registered: ARTIST_ROLLE".to_string() }, err);
+
+        // Built-in roles are listed in the catalog
+        let catalog = list_registered_roles(deps.as_ref(), None, Some(MAX_LIMIT)).unwrap();
+        assert_eq!(8, catalog.roles.len());
+        let fan = catalog.roles.iter().find(|r| r.role == FAN_ROLE).unwrap();
+        assert_eq!(FAN_ASSIGNER_ROLE, fan.admin_role);
+
+        // Registered roles can be granted until they are deprecated
+        registerRole(deps.as_mut(), mock_env(), admin_info.clone(), "VIP_ROLE".to_string(), "VIP guest".to_string(), ORGANIZER_ROLE.to_string()).unwrap();
+        assert_eq!(ORGANIZER_ROLE, query_role_admin(deps.as_ref(), "VIP_ROLE".to_string()).unwrap().admin_role);
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "organizer".to_string(), ORGANIZER_ROLE.to_string(), None).unwrap();
+        let organizer_info = mock_info("organizer", &[]);
+        grantRole(deps.as_mut(), mock_env(), organizer_info.clone(), "guest".to_string(), "VIP_ROLE".to_string(), None).unwrap();
+
+        deprecateRole(deps.as_mut(), mock_env(), admin_info, "VIP_ROLE".to_string()).unwrap();
+        let err = grantRole(deps.as_mut(), mock_env(), organizer_info, "other".to_string(), "VIP_ROLE".to_string(), None).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Role is deprecated: VIP_ROLE".to_string() }, err);
+        assert!(query_has_role(deps.as_ref(), mock_env(), "guest".to_string(), "VIP_ROLE".to_string()).unwrap().result);
+    }
+
+    #[test]
+    fn test_pause_scopes() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        authorizeFanAssigner(deps.as_mut(), mock_env(), admin_info.clone(), "ticketing".to_string(), None, None).unwrap();
+
+        // Freezing fan assignment leaves other grants working
+        let msg = ExecuteMsg::Pause { scope: Some(PAUSE_SCOPE_FAN_ASSIGNMENT.to_string()) };
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+        assert!(is_paused(deps.as_ref(), Some(PAUSE_SCOPE_FAN_ASSIGNMENT.to_string())).unwrap().result);
+        assert!(!is_paused(deps.as_ref(), None).unwrap().result);
+
+        let msg = ExecuteMsg::AssignFanRole { user: "fan".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("ticketing", &[]), msg).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Paused: fan_assignment".to_string() }, err);
+
+        let msg = ExecuteMsg::GrantRole { user: "artist".to_string(), role: ARTIST_ROLE.to_string(), expires_at: None };
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+
+        // Fan roles stay frozen when granted through the generic grant paths
+        let msg = ExecuteMsg::GrantRole { user: "fan".to_string(), role: FAN_ROLE.to_string(), expires_at: None };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("ticketing", &[]), msg).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Paused: fan_assignment".to_string() }, err);
+        let grants = vec![
+            RoleAssignment { user: "artist2".to_string(), role: ARTIST_ROLE.to_string() },
+            RoleAssignment { user: "fan".to_string(), role: FAN_ROLE.to_string() },
+        ];
+        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), ExecuteMsg::BatchGrantRoles { grants }).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Paused: fan_assignment".to_string() }, err);
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), ExecuteMsg::Unpause { scope: Some(PAUSE_SCOPE_FAN_ASSIGNMENT.to_string()) }).unwrap();
+
+        // Pausing grants also stops scoped grants and revocations
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), ExecuteMsg::Pause { scope: Some(PAUSE_SCOPE_GRANTS.to_string()) }).unwrap();
+        let msg = ExecuteMsg::GrantScopedRole { user: "mod".to_string(), role: MODERATOR_ROLE.to_string(), scope: "tribe:1".to_string(), expires_at: None };
+        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Paused: grants".to_string() }, err);
+        let msg = ExecuteMsg::RevokeScopedRole { user: "mod".to_string(), role: MODERATOR_ROLE.to_string(), scope: "tribe:1".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Paused: grants".to_string() }, err);
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), ExecuteMsg::Unpause { scope: Some(PAUSE_SCOPE_GRANTS.to_string()) }).unwrap();
+
+        // A global pause blocks grants but still allows admin recovery
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), ExecuteMsg::Pause { scope: None }).unwrap();
+        let msg = ExecuteMsg::GrantRole { user: "other".to_string(), role: ARTIST_ROLE.to_string(), expires_at: None };
+        let err = execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Contract is paused".to_string() }, err);
+
+        let msg = ExecuteMsg::RevokeRole { user: "artist".to_string(), role: ARTIST_ROLE.to_string() };
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), admin_info, ExecuteMsg::Unpause { scope: None }).unwrap();
+        assert!(!is_paused(deps.as_ref(), None).unwrap().result);
+    }
+
+    #[test]
+    fn test_admin_handover() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        // The last admin can neither renounce nor be revoked
+        let err = renounceRole(deps.as_mut(), mock_env(), admin_info.clone(), DEFAULT_ADMIN_ROLE.to_string()).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Cannot remove the last admin".to_string() }, err);
+        let err = revokeRole(deps.as_mut(), mock_env(), admin_info.clone(), "admin".to_string(), DEFAULT_ADMIN_ROLE.to_string()).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Cannot remove the last admin".to_string() }, err);
+
+        // Rotate the key through a two-step handover
+        proposeAdmin(deps.as_mut(), mock_env(), admin_info.clone(), "newadmin".to_string()).unwrap();
+        let err = acceptAdmin(deps.as_mut(), mock_env(), mock_info("intruder", &[])).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+
+        acceptAdmin(deps.as_mut(), mock_env(), mock_info("newadmin", &[])).unwrap();
+        assert!(query_has_role(deps.as_ref(), mock_env(), "newadmin".to_string(), DEFAULT_ADMIN_ROLE.to_string()).unwrap().result);
+        assert!(!query_has_role(deps.as_ref(), mock_env(), "admin".to_string(), DEFAULT_ADMIN_ROLE.to_string()).unwrap().result);
+        assert_eq!(1, count_role_members(deps.as_ref(), mock_env(), DEFAULT_ADMIN_ROLE.to_string()).unwrap().count);
+        assert_eq!(None, PENDING_ADMIN.may_load(deps.as_ref().storage).unwrap());
+    }
+
+    #[test]
+    fn test_batch_grants() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        updateConfig(deps.as_mut(), mock_env(), admin_info.clone(), Some(3)).unwrap();
+
+        let assignment = |user: &str, role: &str| RoleAssignment { user: user.to_string(), role: role.to_string() };
+
+        // One bad item rejects the whole batch
+        let grants = vec![assignment("alice", ARTIST_ROLE), assignment("bob", "ARTIST_ROLLE")];
+        batchGrantRoles(deps.as_mut(), mock_env(), admin_info.clone(), grants).unwrap_err();
+        assert!(!query_has_role(deps.as_ref(), mock_env(), "alice".to_string(), ARTIST_ROLE.to_string()).unwrap().result);
+
+        // Batches are capped by the configured size
+        let grants = vec![assignment("a1", FAN_ROLE), assignment("a2", FAN_ROLE), assignment("a3", FAN_ROLE), assignment("a4", FAN_ROLE)];
+        let err = batchGrantRoles(deps.as_mut(), mock_env(), admin_info.clone(), grants).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Batch size exceeds limit of 3".to_string() }, err);
+
+        // FAN_ROLE is administered by fan assigners, so the admin needs that role first
+        authorizeFanAssigner(deps.as_mut(), mock_env(), admin_info.clone(), "admin".to_string(), None, None).unwrap();
+        let grants = vec![assignment("alice", ARTIST_ROLE), assignment("alice", FAN_ROLE), assignment("bob", FAN_ROLE)];
+        let res = batchGrantRoles(deps.as_mut(), mock_env(), admin_info.clone(), grants).unwrap();
+        assert_eq!("alice:ARTIST_ROLE", res.attributes[2].value);
+        assert_eq!(2, count_role_members(deps.as_ref(), mock_env(), FAN_ROLE.to_string()).unwrap().count);
+
+        let revocations = vec![assignment("alice", FAN_ROLE), assignment("bob", FAN_ROLE)];
+        batchRevokeRoles(deps.as_mut(), mock_env(), admin_info, revocations).unwrap();
+        assert_eq!(0, count_role_members(deps.as_ref(), mock_env(), FAN_ROLE.to_string()).unwrap().count);
+    }
+
+    #[test]
+    fn test_fan_assigner_allowance() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        let ticketing_info = mock_info("ticketing", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        let expires_at = mock_env().block.time.seconds() + 100;
+        authorizeFanAssigner(deps.as_mut(), mock_env(), admin_info.clone(), "ticketing".to_string(), Some(2), Some(expires_at)).unwrap();
+
+        // The default admin assigns fans without holding FAN_ASSIGNER_ROLE
+        assignFanRole(deps.as_mut(), mock_env(), admin_info.clone(), "fan0".to_string()).unwrap();
+        assert!(query_has_role(deps.as_ref(), mock_env(), "fan0".to_string(), FAN_ROLE.to_string()).unwrap().result);
+
+        assignFanRole(deps.as_mut(), mock_env(), ticketing_info.clone(), "fan1".to_string()).unwrap();
+        let res = get_fan_assigner_allowance(deps.as_ref(), mock_env(), "ticketing".to_string()).unwrap();
+        assert_eq!(Some(1), res.remaining);
+        assert!(res.active);
+
+        // The fan's grant records the assigner
+        let grant = get_role_grant(deps.as_ref(), mock_env(), "fan1".to_string(), FAN_ROLE.to_string()).unwrap();
+        assert_eq!(Addr::unchecked("ticketing"), grant.grant.unwrap().granted_by);
+
+        // Granting FAN_ROLE through GrantRole or a batch counts against the same allowance
+        grantRole(deps.as_mut(), mock_env(), ticketing_info.clone(), "fan2".to_string(), FAN_ROLE.to_string(), None).unwrap();
+        let exhausted = ContractError::CustomError { message: "Fan assignment allowance exhausted".to_string() };
+        let err = grantRole(deps.as_mut(), mock_env(), ticketing_info.clone(), "fan3".to_string(), FAN_ROLE.to_string(), None).unwrap_err();
+        assert_eq!(exhausted, err);
+        let grants = vec![RoleAssignment { user: "fan3".to_string(), role: FAN_ROLE.to_string() }];
+        let err = batchGrantRoles(deps.as_mut(), mock_env(), ticketing_info.clone(), grants).unwrap_err();
+        assert_eq!(exhausted, err);
+        let err = assignFanRole(deps.as_mut(), mock_env(), ticketing_info.clone(), "fan3".to_string()).unwrap_err();
+        assert_eq!(exhausted, err);
+
+        // The assigner role lapses at expiry
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(100);
+        authorizeFanAssigner(deps.as_mut(), mock_env(), admin_info.clone(), "ticketing".to_string(), Some(5), Some(expires_at)).unwrap();
+        assignFanRole(deps.as_mut(), later.clone(), ticketing_info.clone(), "fan3".to_string()).unwrap_err();
+
+        // Extending the assigner role extends its allowance too
+        extendRole(deps.as_mut(), later.clone(), admin_info.clone(), "ticketing".to_string(), FAN_ASSIGNER_ROLE.to_string(), Some(expires_at + 100)).unwrap();
+        assignFanRole(deps.as_mut(), later.clone(), ticketing_info.clone(), "fan3".to_string()).unwrap();
+        assert_eq!(Some(expires_at + 100), get_fan_assigner_allowance(deps.as_ref(), later.clone(), "ticketing".to_string()).unwrap().allowance.unwrap().expires_at);
+
+        // An allowance that has lapsed on its own no longer assigns
+        let mut allowance = FAN_ASSIGNER_ALLOWANCES.load(deps.as_ref().storage, "ticketing").unwrap();
+        allowance.expires_at = Some(expires_at);
+        FAN_ASSIGNER_ALLOWANCES.save(deps.as_mut().storage, "ticketing", &allowance).unwrap();
+        let err = assignFanRole(deps.as_mut(), later.clone(), ticketing_info.clone(), "fan4".to_string()).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Fan assignment allowance expired".to_string() }, err);
+
+        // The generic grant paths cannot create an assigner without an allowance
+        let err = grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "other_app".to_string(), FAN_ASSIGNER_ROLE.to_string(), None).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "FAN_ASSIGNER_ROLE is granted with AuthorizeFanAssigner".to_string() }, err);
+        let grants = vec![RoleAssignment { user: "other_app".to_string(), role: FAN_ASSIGNER_ROLE.to_string() }];
+        batchGrantRoles(deps.as_mut(), mock_env(), admin_info.clone(), grants).unwrap_err();
+
+        // An assigner stored without an allowance has none to spend
+        let grant = RoleGrant { granted_at: 0, granted_by: Addr::unchecked("admin"), expires_at: None };
+        save_role(deps.as_mut().storage, &Addr::unchecked("legacy_app"), FAN_ASSIGNER_ROLE, &grant).unwrap();
+        let err = assignFanRole(deps.as_mut(), mock_env(), mock_info("legacy_app", &[]), "fan4".to_string()).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "No fan assignment allowance".to_string() }, err);
+
+        // Revoking through RevokeRole drops the allowance with the role
+        revokeRole(deps.as_mut(), mock_env(), admin_info.clone(), "ticketing".to_string(), FAN_ASSIGNER_ROLE.to_string()).unwrap();
+        assert_eq!(None, get_fan_assigner_allowance(deps.as_ref(), mock_env(), "ticketing".to_string()).unwrap().allowance);
+        authorizeFanAssigner(deps.as_mut(), mock_env(), admin_info.clone(), "ticketing".to_string(), Some(1), None).unwrap();
+
+        revokeFanAssigner(deps.as_mut(), mock_env(), admin_info, "ticketing".to_string()).unwrap();
+        let res = get_fan_assigner_allowance(deps.as_ref(), mock_env(), "ticketing".to_string()).unwrap();
+        assert_eq!(None, res.allowance);
+        assert!(!res.active);
+        assignFanRole(deps.as_mut(), mock_env(), ticketing_info, "fan3".to_string()).unwrap_err();
+    }
+
+    #[test]
+    fn test_role_hooks() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        addHook(deps.as_mut(), mock_env(), admin_info.clone(), "post_minter".to_string()).unwrap();
+        addHook(deps.as_mut(), mock_env(), admin_info.clone(), "tribe_controller".to_string()).unwrap();
+        addHook(deps.as_mut(), mock_env(), mock_info("anyone", &[]), "evil".to_string()).unwrap_err();
+        assert_eq!(vec!["post_minter", "tribe_controller"], list_hooks(deps.as_ref()).unwrap().hooks);
+
+        // Every hook is notified of a grant
+        let res = grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string(), None).unwrap();
+        assert_eq!(2, res.messages.len());
+        let expected = to_json_binary(&RoleHookMsg::RoleChanged {
+            user: "mod".to_string(),
+            role: MODERATOR_ROLE.to_string(),
+            scope: None,
+            granted: true,
+        })
+        .unwrap();
+        let execute = WasmMsg::Execute { contract_addr: "post_minter".to_string(), msg: expected, funds: vec![] };
+        assert_eq!(SubMsg::reply_on_error(execute, HOOK_REPLY_ID), res.messages[0]);
+
+        // A failing subscriber is reported without reverting the grant
+        let failed = Reply { id: HOOK_REPLY_ID, result: SubMsgResult::Err("unknown variant".to_string()) };
+        let res = reply(deps.as_mut(), mock_env(), failed).unwrap();
+        assert_eq!("role_hook_failed", res.attributes[0].value);
+
+        removeHook(deps.as_mut(), mock_env(), admin_info.clone(), "tribe_controller".to_string()).unwrap();
+        let res = renounceRole(deps.as_mut(), mock_env(), mock_info("mod", &[]), MODERATOR_ROLE.to_string()).unwrap();
+        assert_eq!(1, res.messages.len());
+
+        // Renouncing a role that is not held notifies no one
+        let err = renounceRole(deps.as_mut(), mock_env(), mock_info("mod", &[]), MODERATOR_ROLE.to_string()).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Role not held".to_string() }, err);
+
+        // Lapsed grants can be cleared by anyone once expired, which notifies the hooks
+        let expires_at = mock_env().block.time.seconds() + 100;
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "partner".to_string(), BRAND_ROLE.to_string(), Some(expires_at)).unwrap();
+        let clear = |deps: DepsMut, env: Env| clearExpiredRole(deps, env, mock_info("anyone", &[]), "partner".to_string(), BRAND_ROLE.to_string(), None);
+        let err = clear(deps.as_mut(), mock_env()).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Role has not expired".to_string() }, err);
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(100);
+        let res = clear(deps.as_mut(), later.clone()).unwrap();
+        assert_eq!(1, res.messages.len());
+        assert_eq!(None, get_role_grant(deps.as_ref(), later.clone(), "partner".to_string(), BRAND_ROLE.to_string()).unwrap().grant);
+        clear(deps.as_mut(), later.clone()).unwrap_err();
+
+        // Extending a live grant changes nothing for subscribers; reviving a lapsed one is a grant
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "partner".to_string(), BRAND_ROLE.to_string(), Some(expires_at)).unwrap();
+        let res = extendRole(deps.as_mut(), mock_env(), admin_info.clone(), "partner".to_string(), BRAND_ROLE.to_string(), Some(expires_at + 1)).unwrap();
+        assert!(res.messages.is_empty());
+        let mut revival = later.clone();
+        revival.block.time = revival.block.time.plus_seconds(1);
+        let res = extendRole(deps.as_mut(), revival, admin_info, "partner".to_string(), BRAND_ROLE.to_string(), None).unwrap();
+        let expected = to_json_binary(&RoleHookMsg::RoleChanged {
+            user: "partner".to_string(),
+            role: BRAND_ROLE.to_string(),
+            scope: None,
+            granted: true,
+        })
+        .unwrap();
+        let execute = WasmMsg::Execute { contract_addr: "post_minter".to_string(), msg: expected, funds: vec![] };
+        assert_eq!(vec![SubMsg::reply_on_error(execute, HOOK_REPLY_ID)], res.messages);
+    }
+
+    #[test]
+    fn test_multisig_proposals() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        let admin2_info = mock_info("admin2", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "admin2".to_string(), DEFAULT_ADMIN_ROLE.to_string(), None).unwrap();
+
+        let sensitive = vec![DEFAULT_ADMIN_ROLE.to_string(), MODERATOR_ROLE.to_string()];
+        let err = updateSecurity(deps.as_mut(), mock_env(), admin_info.clone(), sensitive.clone(), 3, 100).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Approval threshold exceeds admin count of 2".to_string() }, err);
+        updateSecurity(deps.as_mut(), mock_env(), admin_info.clone(), sensitive.clone(), 2, 100).unwrap();
+
+        // Sensitive grants become proposals, others still apply directly
+        let res = grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string(), None).unwrap();
+        assert_eq!("create_proposal", res.attributes[0].value);
+        assert!(!query_has_role(deps.as_ref(), mock_env(), "mod".to_string(), MODERATOR_ROLE.to_string()).unwrap().result);
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "artist".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        assert!(query_has_role(deps.as_ref(), mock_env(), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap().result);
+
+        let err = executeProposal(deps.as_mut(), mock_env(), admin_info.clone(), 1).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Not enough approvals: 1/2".to_string() }, err);
+        approveProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 1).unwrap();
+        executeProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 1).unwrap();
+        assert!(query_has_role(deps.as_ref(), mock_env(), "mod".to_string(), MODERATOR_ROLE.to_string()).unwrap().result);
+        executeProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 1).unwrap_err();
+
+        // Proposals lapse after the voting period
+        revokeRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string()).unwrap();
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(100);
+        let err = approveProposal(deps.as_mut(), later, admin2_info.clone(), 2).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Proposal expired".to_string() }, err);
+
+        cancelProposal(deps.as_mut(), mock_env(), admin_info.clone(), 2).unwrap();
+        approveProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 2).unwrap_err();
+        assert_eq!(2, list_proposals(deps.as_ref(), None, None).unwrap().proposals.len());
+
+        // Extending a sensitive grant needs the same approvals as granting it
+        let expires_at = mock_env().block.time.seconds() + 1_000;
+        let res = extendRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string(), Some(expires_at)).unwrap();
+        assert_eq!("create_proposal", res.attributes[0].value);
+        let grant = get_role_grant(deps.as_ref(), mock_env(), "mod".to_string(), MODERATOR_ROLE.to_string()).unwrap().grant.unwrap();
+        assert_eq!(None, grant.expires_at);
+        approveProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 3).unwrap();
+        executeProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 3).unwrap();
+        let grant = get_role_grant(deps.as_ref(), mock_env(), "mod".to_string(), MODERATOR_ROLE.to_string()).unwrap().grant.unwrap();
+        assert_eq!(Some(expires_at), grant.expires_at);
+
+        // Admins cannot drop below the threshold, or no proposal could pass again
+        let err = renounceRole(deps.as_mut(), mock_env(), admin2_info.clone(), DEFAULT_ADMIN_ROLE.to_string()).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Cannot drop below the approval threshold of 2 admins".to_string() }, err);
+
+        // Scoping a sensitive role to one tribe still needs approvals
+        let res = grantScopedRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod3".to_string(), MODERATOR_ROLE.to_string(), "tribe:5".to_string(), None).unwrap();
+        assert_eq!("create_proposal", res.attributes[0].value);
+        assert!(!has_scoped_role(deps.as_ref().storage, &mock_env(), &Addr::unchecked("mod3"), MODERATOR_ROLE, "tribe:5").unwrap());
+        approveProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 4).unwrap();
+        executeProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 4).unwrap();
+        assert!(has_scoped_role(deps.as_ref().storage, &mock_env(), &Addr::unchecked("mod3"), MODERATOR_ROLE, "tribe:5").unwrap());
+
+        // Batches cannot bypass the threshold
+        let grants = vec![RoleAssignment { user: "mod2".to_string(), role: MODERATOR_ROLE.to_string() }];
+        batchGrantRoles(deps.as_mut(), mock_env(), admin_info.clone(), grants).unwrap_err();
+
+        // Neither can building a new power role out of the registry and permission matrix
+        let res = registerRole(deps.as_mut(), mock_env(), admin_info.clone(), "SHADOW_ROLE".to_string(), "".to_string(), ARTIST_ROLE.to_string()).unwrap();
+        assert_eq!("create_proposal", res.attributes[0].value);
+        assert!(!ROLE_REGISTRY.has(deps.as_ref().storage, "SHADOW_ROLE"));
+        let res = setPermission(deps.as_mut(), mock_env(), admin_info.clone(), ACTION_BLOCK.to_string(), vec![ARTIST_ROLE.to_string()]).unwrap();
+        assert_eq!("create_proposal", res.attributes[0].value);
+        let res = setRoleAdmin(deps.as_mut(), mock_env(), admin_info.clone(), MODERATOR_ROLE.to_string(), ARTIST_ROLE.to_string()).unwrap();
+        assert_eq!("create_proposal", res.attributes[0].value);
+        assert_eq!(DEFAULT_ADMIN_ROLE, get_role_admin(deps.as_ref().storage, MODERATOR_ROLE).unwrap());
+        approveProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 7).unwrap();
+        executeProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 7).unwrap();
+        assert_eq!(ARTIST_ROLE, get_role_admin(deps.as_ref().storage, MODERATOR_ROLE).unwrap());
+
+        // The admin handover is only offered once approved
+        let res = proposeAdmin(deps.as_mut(), mock_env(), admin_info.clone(), "admin3".to_string()).unwrap();
+        assert_eq!("create_proposal", res.attributes[0].value);
+        let err = acceptAdmin(deps.as_mut(), mock_env(), mock_info("admin3", &[])).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "No pending admin".to_string() }, err);
+        approveProposal(deps.as_mut(), mock_env(), admin2_info.clone(), 8).unwrap();
+        executeProposal(deps.as_mut(), mock_env(), admin2_info, 8).unwrap();
+        acceptAdmin(deps.as_mut(), mock_env(), mock_info("admin3", &[])).unwrap();
+        assert!(!has_role(deps.as_ref().storage, &mock_env(), &Addr::unchecked("admin"), DEFAULT_ADMIN_ROLE).unwrap());
+    }
+
+    #[test]
+    fn test_audit_log() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), MODERATOR_ROLE.to_string(), None).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "bob".to_string(), MODERATOR_ROLE.to_string(), None).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        revokeRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), MODERATOR_ROLE.to_string()).unwrap();
+
+        let entries = query_audit_log(deps.as_ref(), None, None, None, None).unwrap().entries;
+        assert_eq!(4, entries.len());
+        assert_eq!(Addr::unchecked("admin"), entries[0].actor);
+        assert_eq!(mock_env().block.height, entries[0].block_height);
+
+        let entries = query_audit_log(deps.as_ref(), Some("alice".to_string()), None, None, None).unwrap().entries;
+        assert_eq!(vec![1, 3, 4], entries.iter().map(|entry| entry.id).collect::<Vec<_>>());
+
+        // Who made alice a moderator, and who removed it
+        let entries = query_audit_log(deps.as_ref(), Some("alice".to_string()), Some(MODERATOR_ROLE.to_string()), None, None).unwrap().entries;
+        assert_eq!(vec!["grant_role", "revoke_role"], entries.iter().map(|entry| entry.action.as_str()).collect::<Vec<_>>());
+
+        let entries = query_audit_log(deps.as_ref(), None, Some(MODERATOR_ROLE.to_string()), Some(1), Some(1)).unwrap().entries;
+        assert_eq!(Addr::unchecked("bob"), entries[0].target);
+        assert_eq!(1, entries.len());
+
+        // Expiry changes, admin rewiring and blocks are logged too; a failed renounce is not
+        let expires_at = mock_env().block.time.seconds() + 100;
+        extendRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), ARTIST_ROLE.to_string(), Some(expires_at)).unwrap();
+        setRoleAdmin(deps.as_mut(), mock_env(), admin_info.clone(), ARTIST_ROLE.to_string(), MODERATOR_ROLE.to_string()).unwrap();
+        block(deps.as_mut(), mock_env(), admin_info.clone(), "bob".to_string(), "spam".to_string(), None).unwrap();
+        unblock(deps.as_mut(), mock_env(), admin_info, "bob".to_string()).unwrap();
+        renounceRole(deps.as_mut(), mock_env(), mock_info("alice", &[]), MODERATOR_ROLE.to_string()).unwrap_err();
+        let entries = query_audit_log(deps.as_ref(), None, None, Some(4), None).unwrap().entries;
+        assert_eq!(
+            vec!["extend_role", "set_role_admin", "block", "unblock"],
+            entries.iter().map(|entry| entry.action.as_str()).collect::<Vec<_>>()
+        );
+
+        // Role-less events are still reachable through the role index
+        let entries = query_audit_log(deps.as_ref(), None, Some(AUDIT_ROLE_BLOCKLIST.to_string()), None, None).unwrap().entries;
+        assert_eq!(vec!["block", "unblock"], entries.iter().map(|entry| entry.action.as_str()).collect::<Vec<_>>());
+        let entries = query_audit_log(deps.as_ref(), None, Some(ARTIST_ROLE.to_string()), None, None).unwrap().entries;
+        assert_eq!(Some("set_role_admin"), entries.last().map(|entry| entry.action.as_str()));
+    }
+
+    #[test]
+    fn test_claim_role_voucher() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        // ed25519 key and voucher signature produced off-chain for the mock contract and chain
+        let pubkey = hex::decode("03a107bff3ce10be1d70dd18e74bc09967e4d6309ba50d5f1ddc8664125531b8").unwrap();
+        let signature = Binary::from(hex::decode("05ff57c0c2411b7efb547e84d2ce605c86bc2f3f507b6a54963b37eab1fe097a376cdcdeb53282d38e3630d636cf34afc372829c666d51a1fa5f77ed83b1b60a").unwrap());
+        let expires_at = mock_env().block.time.seconds() + 1000;
+
+        registerVoucherSigner(deps.as_mut(), mock_env(), admin_info.clone(), "qr".to_string(), Binary::from(pubkey), KeyType::Ed25519, vec![FAN_ROLE.to_string()]).unwrap();
+
+        let claim = |role: &str| ExecuteMsg::ClaimRole {
+            signer: "qr".to_string(),
+            role: role.to_string(),
+            expires_at,
+            nonce: 7,
+            max_claims: 2,
+            signature: signature.clone(),
+        };
+
+        let res = execute(deps.as_mut(), mock_env(), mock_info("fan1", &[]), claim(FAN_ROLE)).unwrap();
+        assert_eq!("qr", res.attributes[3].value);
+        assert!(query_has_role(deps.as_ref(), mock_env(), "fan1".to_string(), FAN_ROLE.to_string()).unwrap().result);
+
+        // Same user cannot replay, and the voucher is capped at max_claims
+        let err = execute(deps.as_mut(), mock_env(), mock_info("fan1", &[]), claim(FAN_ROLE)).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Voucher already claimed".to_string() }, err);
+        execute(deps.as_mut(), mock_env(), mock_info("fan2", &[]), claim(FAN_ROLE)).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), mock_info("fan3", &[]), claim(FAN_ROLE)).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Voucher fully claimed".to_string() }, err);
+
+        // The signer is limited to its roles, and the signature does not cover other vouchers
+        let err = execute(deps.as_mut(), mock_env(), mock_info("fan3", &[]), claim(ARTIST_ROLE)).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Signer cannot issue vouchers for ARTIST_ROLE".to_string() }, err);
+        let forged = ExecuteMsg::ClaimRole { signer: "qr".to_string(), role: FAN_ROLE.to_string(), expires_at, nonce: 8, max_claims: 2, signature: signature.clone() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("fan3", &[]), forged).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Invalid voucher signature".to_string() }, err);
+        let unknown = ExecuteMsg::ClaimRole { signer: "nfc".to_string(), role: FAN_ROLE.to_string(), expires_at, nonce: 7, max_claims: 2, signature };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("fan3", &[]), unknown).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Voucher signer not registered".to_string() }, err);
+    }
+
+    #[test]
+    fn test_scoped_roles() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        let has_scoped = |deps: Deps, user: &str, scope: &str| {
+            has_scoped_role(deps.storage, &mock_env(), &Addr::unchecked(user), MODERATOR_ROLE, scope).unwrap()
+        };
+
+        grantScopedRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string(), "tribe:5".to_string(), None).unwrap();
+        assert!(has_scoped(deps.as_ref(), "mod", "tribe:5"));
+        assert!(!has_scoped(deps.as_ref(), "mod", "tribe:6"));
+        assert!(!query_has_role(deps.as_ref(), mock_env(), "mod".to_string(), MODERATOR_ROLE.to_string()).unwrap().result);
+
+        // Global grants satisfy every scope
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "global".to_string(), MODERATOR_ROLE.to_string(), None).unwrap();
+        assert!(has_scoped(deps.as_ref(), "global", "tribe:6"));
+
+        let err = grantScopedRole(deps.as_mut(), mock_env(), mock_info("mod", &[]), "other".to_string(), MODERATOR_ROLE.to_string(), "tribe:5".to_string(), None).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+
+        revokeScopedRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string(), "tribe:5".to_string()).unwrap();
+        assert!(!has_scoped(deps.as_ref(), "mod", "tribe:5"));
+        revokeScopedRole(deps.as_mut(), mock_env(), admin_info, "mod".to_string(), MODERATOR_ROLE.to_string(), "tribe:5".to_string()).unwrap_err();
+
+        let entries = query_audit_log(deps.as_ref(), Some("mod".to_string()), None, None, None).unwrap().entries;
+        assert_eq!(Some("tribe:5".to_string()), entries[0].scope);
+    }
+
+    #[test]
+    fn test_permission_matrix() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        let can = |deps: Deps, user: &str, action: &str, scope: Option<&str>| {
+            can_perform(deps.storage, &mock_env(), &Addr::unchecked(user), action, scope).unwrap()
+        };
+
+        assert!(can(deps.as_ref(), "admin", "post_minter.set_cooldown", None));
+        assert!(!can(deps.as_ref(), "artist", "post_minter.set_cooldown", None));
+        assert!(!can(deps.as_ref(), "admin", "unknown.action", None));
+
+        // Scoped moderators can moderate their own tribe only
+        grantScopedRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string(), "tribe:5".to_string(), None).unwrap();
+        assert!(can(deps.as_ref(), "mod", "tribe_controller.moderate", Some("tribe:5")));
+        assert!(!can(deps.as_ref(), "mod", "tribe_controller.moderate", Some("tribe:6")));
+
+        let err = setPermission(deps.as_mut(), mock_env(), admin_info.clone(), "post_minter.set_cooldown".to_string(), vec!["NOPE_ROLE".to_string()]).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Role not registered: NOPE_ROLE".to_string() }, err);
+        setPermission(deps.as_mut(), mock_env(), admin_info.clone(), "post_minter.set_cooldown".to_string(), vec![ARTIST_ROLE.to_string()]).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "artist".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        assert!(can(deps.as_ref(), "artist", "post_minter.set_cooldown", None));
+        assert!(!can(deps.as_ref(), "admin", "post_minter.set_cooldown", None));
+
+        removePermission(deps.as_mut(), mock_env(), admin_info, "post_minter.set_cooldown".to_string()).unwrap();
+        assert!(!can(deps.as_ref(), "artist", "post_minter.set_cooldown", None));
+        assert_eq!(7, list_permissions(deps.as_ref(), None, None).unwrap().permissions.len());
+    }
+
+    #[test]
+    fn test_blocklist() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        let mod_info = mock_info("mod", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), MODERATOR_ROLE.to_string(), None).unwrap();
+
+        let blocked = |deps: Deps, user: &str, env: Env| is_blocked(deps.storage, &env, &Addr::unchecked(user)).unwrap();
+
+        let until = mock_env().block.time.seconds() + 100;
+        block(deps.as_mut(), mock_env(), mod_info.clone(), "spammer".to_string(), "spam".to_string(), Some(until)).unwrap();
+        assert!(blocked(deps.as_ref(), "spammer", mock_env()));
+
+        // Temporary blocks lapse on their own
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(100);
+        assert!(!blocked(deps.as_ref(), "spammer", later));
+
+        let err = block(deps.as_mut(), mock_env(), mod_info.clone(), "admin".to_string(), "coup".to_string(), None).unwrap_err();
+        assert_eq!(ContractError::CustomError { message: "Cannot block an admin".to_string() }, err);
+        let err = block(deps.as_mut(), mock_env(), mock_info("anyone", &[]), "spammer".to_string(), "".to_string(), None).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+
+        // Blocked users lose their permissions
+        block(deps.as_mut(), mock_env(), admin_info.clone(), "mod".to_string(), "compromised".to_string(), None).unwrap();
+        assert!(!can_perform(deps.as_ref().storage, &mock_env(), &Addr::unchecked("mod"), ACTION_BLOCK, None).unwrap());
+        unblock(deps.as_mut(), mock_env(), mod_info, "spammer".to_string()).unwrap_err();
+
+        // A mistaken block can be lifted during a pause
+        execute(deps.as_mut(), mock_env(), admin_info.clone(), ExecuteMsg::Pause { scope: None }).unwrap();
+        execute(deps.as_mut(), mock_env(), admin_info, ExecuteMsg::Unblock { user: "mod".to_string() }).unwrap();
+        assert!(!blocked(deps.as_ref(), "mod", mock_env()));
+    }
+
+    #[test]
+    fn test_role_applications() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        let artist_info = mock_info("artist", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        let application = |deps: Deps, applicant: &str| {
+            APPLICATIONS.load(deps.storage, (ARTIST_ROLE, applicant)).unwrap()
+        };
+
+        applyForRole(deps.as_mut(), mock_env(), artist_info.clone(), ARTIST_ROLE.to_string(), "ipfs://portfolio".to_string()).unwrap();
+        applyForRole(deps.as_mut(), mock_env(), artist_info.clone(), ARTIST_ROLE.to_string(), "ipfs://again".to_string()).unwrap_err();
+        applyForRole(deps.as_mut(), mock_env(), mock_info("other", &[]), ARTIST_ROLE.to_string(), "ipfs://other".to_string()).unwrap();
+
+        // Only the role's admins review
+        let err = approveApplication(deps.as_mut(), mock_env(), mock_info("other", &[]), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err);
+
+        approveApplication(deps.as_mut(), mock_env(), admin_info.clone(), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap();
+        assert!(query_has_role(deps.as_ref(), mock_env(), "artist".to_string(), ARTIST_ROLE.to_string()).unwrap().result);
+        assert_eq!(ApplicationStatus::Approved, application(deps.as_ref(), "artist").status);
+
+        rejectApplication(deps.as_mut(), mock_env(), admin_info, "other".to_string(), ARTIST_ROLE.to_string(), "no portfolio".to_string()).unwrap();
+        assert_eq!(Some("no portfolio".to_string()), application(deps.as_ref(), "other").reason);
+        let entries = query_audit_log(deps.as_ref(), Some("other".to_string()), None, None, None).unwrap().entries;
+        assert_eq!("reject_application", entries[0].action);
+
+        let pending = list_applications(deps.as_ref(), ARTIST_ROLE.to_string(), Some(ApplicationStatus::Pending), None, None).unwrap();
+        assert!(pending.applications.is_empty());
+        assert_eq!(2, list_applications(deps.as_ref(), ARTIST_ROLE.to_string(), None, None, None).unwrap().applications.len());
+
+        // Rejected applicants may try again
+        applyForRole(deps.as_mut(), mock_env(), mock_info("other", &[]), ARTIST_ROLE.to_string(), "ipfs://better".to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_role_badges() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        setBadgeUri(deps.as_mut(), mock_env(), admin_info.clone(), ARTIST_ROLE.to_string(), Some("ipfs://artist-badge".to_string())).unwrap();
+        setBadgeUri(deps.as_mut(), mock_env(), admin_info.clone(), MODERATOR_ROLE.to_string(), Some("ipfs://nope".to_string())).unwrap_err();
+
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), ARTIST_ROLE.to_string(), None).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), BRAND_ROLE.to_string(), None).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), MODERATOR_ROLE.to_string(), None).unwrap();
+
+        let tokens = badge_tokens(deps.as_ref(), mock_env(), "alice".to_string(), None, None).unwrap().tokens;
+        assert_eq!(vec!["ARTIST_ROLE:alice", "BRAND_ROLE:alice"], tokens);
+        let tokens = badge_tokens(deps.as_ref(), mock_env(), "alice".to_string(), Some("ARTIST_ROLE:alice".to_string()), None).unwrap().tokens;
+        assert_eq!(vec!["BRAND_ROLE:alice"], tokens);
+
+        let info = badge_nft_info(deps.as_ref(), mock_env(), "ARTIST_ROLE:alice".to_string()).unwrap();
+        assert_eq!(Some("ipfs://artist-badge".to_string()), info.token_uri);
+        assert_eq!("alice", badge_owner_of(deps.as_ref(), mock_env(), "ARTIST_ROLE:alice".to_string()).unwrap().owner);
+
+        // A lapsed grant's badge is no longer reported
+        let expires_at = mock_env().block.time.seconds() + 100;
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "bob".to_string(), ARTIST_ROLE.to_string(), Some(expires_at)).unwrap();
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(100);
+        assert_eq!(vec!["ARTIST_ROLE:bob"], badge_tokens(deps.as_ref(), mock_env(), "bob".to_string(), None, None).unwrap().tokens);
+        assert!(badge_tokens(deps.as_ref(), later.clone(), "bob".to_string(), None, None).unwrap().tokens.is_empty());
+        badge_owner_of(deps.as_ref(), later, "ARTIST_ROLE:bob".to_string()).unwrap_err();
+
+        // Revoking or renouncing burns the badge
+        revokeRole(deps.as_mut(), mock_env(), admin_info, "alice".to_string(), ARTIST_ROLE.to_string()).unwrap();
+        renounceRole(deps.as_mut(), mock_env(), mock_info("alice", &[]), BRAND_ROLE.to_string()).unwrap();
+        assert!(badge_tokens(deps.as_ref(), mock_env(), "alice".to_string(), None, None).unwrap().tokens.is_empty());
+        badge_owner_of(deps.as_ref(), mock_env(), "ARTIST_ROLE:alice".to_string()).unwrap_err();
+    }
+
+    #[test]
+    fn test_session_keys() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+
+        let owner_info = mock_info("alice", &[]);
+        let expires_at = mock_env().block.time.seconds() + 3600;
+        let actions = vec![post_minter::SESSION_ACTION_POST.to_string()];
+        registerSessionKey(deps.as_mut(), mock_env(), owner_info.clone(), "alice".to_string(), actions.clone(), expires_at).unwrap_err();
+        registerSessionKey(deps.as_mut(), mock_env(), owner_info.clone(), "phone".to_string(), vec![], expires_at).unwrap_err();
+        registerSessionKey(deps.as_mut(), mock_env(), owner_info.clone(), "phone".to_string(), actions, expires_at).unwrap();
+
+        let authorized = |deps: Deps, env: &Env, action: &str| {
+            is_session_authorized(deps.storage, env, &Addr::unchecked("alice"), &Addr::unchecked("phone"), action).unwrap()
+        };
+        assert!(authorized(deps.as_ref(), &mock_env(), post_minter::SESSION_ACTION_POST));
+        assert!(!authorized(deps.as_ref(), &mock_env(), tribe_controller::SESSION_ACTION_JOIN));
+        let keys = list_session_keys(deps.as_ref(), "alice".to_string(), None, None).unwrap();
+        assert_eq!(1, keys.session_keys.len());
+
+        // Expired keys stop working
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(3600);
+        assert!(!authorized(deps.as_ref(), &later, post_minter::SESSION_ACTION_POST));
+
+        // Blocking the owner disables their sessions
+        block(deps.as_mut(), mock_env(), admin_info.clone(), "alice".to_string(), "spam".to_string(), None).unwrap();
+        assert!(!authorized(deps.as_ref(), &mock_env(), post_minter::SESSION_ACTION_POST));
+        unblock(deps.as_mut(), mock_env(), admin_info, "alice".to_string()).unwrap();
+
+        revokeSessionKey(deps.as_mut(), owner_info.clone(), "phone".to_string()).unwrap();
+        assert!(!authorized(deps.as_ref(), &mock_env(), post_minter::SESSION_ACTION_POST));
+        revokeSessionKey(deps.as_mut(), owner_info, "phone".to_string()).unwrap_err();
+    }
+
+    #[test]
+    fn test_pause_all() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        grantRole(deps.as_mut(), mock_env(), admin_info.clone(), "guardian".to_string(), GUARDIAN_ROLE.to_string(), None).unwrap();
+        registerPlatformContract(deps.as_mut(), mock_env(), admin_info.clone(), "posts".to_string(), "post_minter".to_string(), PlatformContractKind::PostMinter).unwrap();
+        registerPlatformContract(deps.as_mut(), mock_env(), admin_info.clone(), "profiles".to_string(), "profile_nft_minter".to_string(), PlatformContractKind::ProfileNftMinter).unwrap();
+        registerPlatformContract(deps.as_mut(), mock_env(), admin_info.clone(), "tribes".to_string(), "tribe_controller".to_string(), PlatformContractKind::TribeController).unwrap();
+
+        pauseAll(deps.as_mut(), mock_env(), mock_info("alice", &[])).unwrap_err();
+        let res = pauseAll(deps.as_mut(), mock_env(), mock_info("guardian", &[])).unwrap();
+        let expected: Vec<CosmosMsg> = vec![
+            WasmMsg::Execute { contract_addr: "posts".to_string(), msg: to_json_binary(&PlatformPauseMsg::Pause {}).unwrap(), funds: vec![] }.into(),
+            WasmMsg::Execute { contract_addr: "profiles".to_string(), msg: to_json_binary(&PlatformPauseMsg::SetPaused { paused: true }).unwrap(), funds: vec![] }.into(),
+            WasmMsg::Execute { contract_addr: "tribes".to_string(), msg: to_json_binary(&PlatformPauseMsg::Pause {}).unwrap(), funds: vec![] }.into(),
+        ];
+        assert!(res.messages.iter().all(|sub| sub.id == PLATFORM_PAUSE_REPLY_ID && sub.reply_on == cosmwasm_std::ReplyOn::Error));
+        assert_eq!(expected, res.messages.into_iter().map(|sub| sub.msg).collect::<Vec<_>>());
+        assert!(PAUSED.load(deps.as_ref().storage).unwrap());
+
+        // Only admins can lift the platform-wide pause
+        unpauseAll(deps.as_mut(), mock_env(), mock_info("guardian", &[])).unwrap_err();
+        let res = unpauseAll(deps.as_mut(), mock_env(), admin_info).unwrap();
+        assert_eq!(3, res.messages.len());
+
+        deps.querier.update_wasm(|query| match query {
+            cosmwasm_std::WasmQuery::Smart { contract_addr, .. } if contract_addr == "posts" => {
+                cosmwasm_std::SystemResult::Ok(cosmwasm_std::ContractResult::Ok(to_json_binary(&BoolResponse { result: true }).unwrap()))
+            }
+            _ => cosmwasm_std::SystemResult::Err(cosmwasm_std::SystemError::Unknown {}),
+        });
+        let status = platform_status(deps.as_ref()).unwrap();
+        assert!(!status.role_manager_paused);
+        assert_eq!(Some(true), status.contracts[0].paused);
+        assert_eq!(None, status.contracts[1].paused);
+    }
+
+    #[test]
+    fn test_pause_all_reaches_platform_contracts() {
+        let mut deps = mock_dependencies();
+        let admin_info = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), admin_info.clone(), InstantiateMsg {}).unwrap();
+        let role_manager = mock_env().contract.address.to_string();
+
+        // A post minter and a profile minter wired to this role_manager, plus a stale registration
+        let mut posts = mock_dependencies();
+        post_minter::instantiate(posts.as_mut(), mock_env(), mock_info("admin", &[]), post_minter::InstantiateMsg {
+            role_manager: role_manager.clone(),
+            tribe_controller: "tribes".to_string(),
+            collectible_controller: "collectibles".to_string(),
+            feed_manager: "feeds".to_string(),
+        }).unwrap();
+        let mut profiles = mock_dependencies();
+        profile_nft_minter::instantiate(profiles.as_mut(), mock_env(), mock_info("admin", &[]), profile_nft_minter::InstantiateMsg {
+            role_manager: role_manager.clone(),
+            name: "Profiles".to_string(),
+            symbol: "PRO".to_string(),
+        }).unwrap();
+        registerPlatformContract(deps.as_mut(), mock_env(), admin_info.clone(), "posts".to_string(), "post_minter".to_string(), PlatformContractKind::PostMinter).unwrap();
+        registerPlatformContract(deps.as_mut(), mock_env(), admin_info.clone(), "profiles".to_string(), "profile_nft_minter".to_string(), PlatformContractKind::ProfileNftMinter).unwrap();
+        registerPlatformContract(deps.as_mut(), mock_env(), admin_info.clone(), "retired".to_string(), "tribe_controller".to_string(), PlatformContractKind::TribeController).unwrap();
+
+        // Deliver each fan-out message as role_manager, replying to the ones that fail
+        let res = pauseAll(deps.as_mut(), mock_env(), admin_info).unwrap();
+        let relay_info = mock_info(&role_manager, &[]);
+        for sub in res.messages {
+            let CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, msg, .. }) = sub.msg else { panic!("unexpected message") };
+            let result = match contract_addr.as_str() {
+                "posts" => post_minter::execute(posts.as_mut(), mock_env(), relay_info.clone(), from_json(&msg).unwrap()),
+                "profiles" => profile_nft_minter::execute(profiles.as_mut(), mock_env(), relay_info.clone(), from_json(&msg).unwrap()),
+                _ => Err(cosmwasm_std::StdError::generic_err("no such contract")),
+            };
+            if let Err(err) = result {
+                let failed = Reply { id: sub.id, result: SubMsgResult::Err(err.to_string()) };
+                let res = reply(deps.as_mut(), mock_env(), failed).unwrap();
+                assert_eq!("platform_pause_failed", res.attributes[0].value);
+            }
+        }
+
+        // Both live contracts are paused despite the stale one
+        let err = post_minter::execute(posts.as_mut(), mock_env(), mock_info("alice", &[]), post_minter::ExecuteMsg::ReportPost {
+            post_id: 0,
+            reason: "spam".to_string(),
+        }).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Contract is paused"), err);
+        let err = profile_nft_minter::execute(profiles.as_mut(), mock_env(), mock_info("alice", &[]), profile_nft_minter::ExecuteMsg::CreateProfile {
+            username: "alice".to_string(),
+            metadata_uri: "ipfs://alice".to_string(),
+        }).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Contract is paused"), err);
+        let err = profile_nft_minter::execute(profiles.as_mut(), mock_env(), mock_info("alice", &[]), profile_nft_minter::ExecuteMsg::TransferNft {
+            recipient: "bob".to_string(),
+            token_id: 0,
+        }).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Contract is paused"), err);
+    }
+}
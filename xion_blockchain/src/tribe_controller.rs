@@ -1,14 +1,39 @@
+// Handlers keep the camelCase names of the Solidity contracts they port
+#![allow(non_snake_case)]
+
 use cosmwasm_std::{
     to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Addr, 
     entry_point, Storage, Order, Uint128, SubMsg, WasmMsg, CosmosMsg, BankMsg, Coin,
 };
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::convert::TryFrom;
 use crate::errors::ContractError;
-use hex;
+
+// Action checked against role_manager's permission matrix
+pub const ACTION_MODERATE: &str = "tribe_controller.moderate";
+
+// Action a session key can be granted in role_manager
+pub const SESSION_ACTION_JOIN: &str = "tribe_controller.join";
+
+// Permission-matrix action allowed to set the protocol fee
+pub const ACTION_SET_PROTOCOL_FEE: &str = "tribe_controller.set_protocol_fee";
+
+// Protocol fee is expressed in basis points and capped at 10%
+const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+// Pagination defaults for list queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// Source members processed per ExecuteMerge call, so large tribes merge across several txs
+const DEFAULT_MERGE_CHUNK: u32 = 50;
+const MAX_MERGE_CHUNK: u32 = 200;
+
+// Legacy entries converted per migrate call; re-run migrate until it reports completed
+const DEFAULT_MIGRATE_LIMIT: u32 = 500;
 
 // Define structs and enums that match the Solidity contract
 
@@ -56,18 +81,29 @@ pub struct InviteCode {
     pub expiry_time: u64,
 }
 
+impl InviteCode {
+    pub fn is_live(&self, now: u64) -> bool {
+        self.used_count < self.max_uses && now < self.expiry_time
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MergeRequest {
     pub source_tribe_id: u64,
     pub target_tribe_id: u64,
     pub request_time: u64,
     pub approved: bool,
+    // Set once the first chunk has run; the merge can no longer be cancelled
+    #[serde(default)]
+    pub in_progress: bool,
+    #[serde(default)]
+    pub moved_members: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TribeConfigView {
     pub join_type: JoinType,
-    pub entry_fee: Uint128,
+    pub entry_fee: Option<Coin>,
     pub nft_requirements: Vec<NFTRequirement>,
     pub can_merge: bool,
 }
@@ -77,21 +113,66 @@ pub struct Config {
     pub role_manager: Addr,
     pub next_tribe_id: u64,
     pub next_merge_request_id: u64,
+    // Share of incoming treasury funds sent to `fee_collector`
+    #[serde(default)]
+    pub protocol_fee_bps: u16,
+    #[serde(default)]
+    pub fee_collector: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TreasuryEntryKind {
+    EntryFee,
+    Deposit,
+    Withdrawal,
+    // Balances carried over when a tribe is merged
+    MergeOut,
+    MergeIn,
+}
+
+// One movement of a tribe's treasury; `protocol_fee` is the part skimmed off incoming funds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryEntry {
+    pub id: u64,
+    pub kind: TreasuryEntryKind,
+    pub amount: Coin,
+    pub protocol_fee: Uint128,
+    pub counterparty: Addr,
+    pub timestamp: u64,
 }
 
 // Storage definitions using cw-storage-plus
 const CONFIG: Item<Config> = Item::new("config");
 const TRIBES: Map<&[u8], TribeData> = Map::new("tribes");
 const TRIBE_META: Map<&str, TribeMeta> = Map::new("tribe_meta");
-const MEMBER_STATUS: Map<&str, MemberStatus> = Map::new("member_status");
+// Number of members per tribe, maintained by set_member_status/remove_member
 const MEMBER_COUNT: Map<&str, u64> = Map::new("member_count");
-const IS_MEMBER: Map<&str, bool> = Map::new("is_member");
 const MERGE_REQUEST: Map<&str, MergeRequest> = Map::new("merge_request");
-const INVITE_CODE: Map<&str, InviteCode> = Map::new("invite_code");
+// Invite codes keyed by (tribe, sha256(code)); the plain code is never stored
+const INVITE_CODE: Map<(u64, &[u8]), InviteCode> = Map::new("invite_codes");
 const TRIBE_COUNT: Item<u64> = Item::new("tribe_count");
-const TRIBE_MEMBERS: Map<(Vec<u8>, &Addr), TribeMember> = Map::new("tribe_members");
-const USER_TRIBES: Map<&Addr, Vec<u64>> = Map::new("user_tribes");
+// Pre-IndexedMap membership storage, only read by `migrate`
+const LEGACY_MEMBER_STATUS: Map<&str, MemberStatus> = Map::new("member_status");
+const LEGACY_IS_MEMBER: Map<&str, bool> = Map::new("is_member");
+const LEGACY_TRIBE_MEMBERS: Map<(Vec<u8>, &Addr), TribeMember> = Map::new("tribe_members");
+const LEGACY_USER_TRIBES: Map<&Addr, Vec<u64>> = Map::new("user_tribes");
+// Invite codes keyed by "{tribe_id}:{hex(code_hash)}", only read by `migrate`
+const LEGACY_INVITE_CODE: Map<&str, InviteCode> = Map::new("invite_code");
+// Set while a migration is unfinished; execute is refused until it completes
+const MIGRATION: Item<MigrationState> = Item::new("migration");
+// Tribes whose MEMBER_COUNT was reset by the unfinished migration
+const MIGRATION_RECOUNTED: Map<u64, ()> = Map::new("migration_recounted");
 const WHITELIST: Map<&Addr, bool> = Map::new("whitelist");
+// Treasury balances per (tribe, denom)
+const TREASURY: Map<(u64, &str), Uint128> = Map::new("treasury");
+const TREASURY_HISTORY: Map<(u64, u64), TreasuryEntry> = Map::new("treasury_history");
+const NEXT_TREASURY_ENTRY_ID: Item<u64> = Item::new("next_treasury_entry_id");
+// Entry fees paid with a join request, held until the request is approved or rejected
+const ESCROWED_FEES: Map<(u64, &Addr), Coin> = Map::new("escrowed_fees");
+const PAUSED: Item<bool> = Item::new("paused");
+// Raw view of TRIBE_META that also accepts the pre-denom layout, only read by `migrate`
+const STORED_TRIBE_META: Map<&str, StoredTribeMeta> = Map::new("tribe_meta");
 
 // Tribe metadata that doesn't fit in the main mapping structure
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -101,11 +182,37 @@ pub struct TribeMeta {
     pub admin: Addr,
     pub whitelist: Vec<Addr>,
     pub join_type: JoinType,
-    pub entry_fee: Uint128,
+    // None for free tribes
+    pub entry_fee: Option<Coin>,
     pub nft_requirements: Vec<NFTRequirement>,
     pub can_merge: bool,
     pub is_active: bool,
     pub member_count: Option<u64>,
+    // Tribe this one was merged into, if any
+    #[serde(default)]
+    pub merged_into: Option<u64>,
+}
+
+// TribeMeta as stored when entry fees were a bare amount
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct LegacyTribeMeta {
+    name: String,
+    metadata: String,
+    admin: Addr,
+    whitelist: Vec<Addr>,
+    join_type: JoinType,
+    entry_fee: Uint128,
+    nft_requirements: Vec<NFTRequirement>,
+    can_merge: bool,
+    is_active: bool,
+    member_count: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+enum StoredTribeMeta {
+    Current(TribeMeta),
+    Legacy(LegacyTribeMeta),
 }
 
 // Add the missing TribeData struct definition
@@ -118,7 +225,7 @@ pub struct TribeData {
     pub nft_contract: Option<Addr>,
     pub nft_type: Option<NFTType>,
     pub nft_required: Option<u64>,
-    pub entry_fee: Uint128,
+    pub entry_fee: Option<Coin>,
     pub is_active: bool,
 }
 
@@ -131,6 +238,32 @@ pub struct TribeMember {
     pub joined_at: u64,
 }
 
+pub struct MemberIndexes<'a> {
+    pub user: MultiIndex<'a, Addr, TribeMember, (u64, Addr)>,
+    // (tribe_id, status, joined_at) so a tribe's members or pending requests page in join order
+    pub joined: MultiIndex<'a, (u64, String, u64), TribeMember, (u64, Addr)>,
+}
+
+impl IndexList<TribeMember> for MemberIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TribeMember>> + '_> {
+        let v: Vec<&dyn Index<TribeMember>> = vec![&self.user, &self.joined];
+        Box::new(v.into_iter())
+    }
+}
+
+// Membership keyed by (tribe_id, member)
+fn members<'a>() -> IndexedMap<'a, (u64, &'a Addr), TribeMember, MemberIndexes<'a>> {
+    let indexes = MemberIndexes {
+        user: MultiIndex::new(|_pk, member| member.member.clone(), "members", "members__user"),
+        joined: MultiIndex::new(
+            |_pk, member| (member.tribe_id, member_status_key(&member.status), member.joined_at),
+            "members",
+            "members__joined",
+        ),
+    };
+    IndexedMap::new("members", indexes)
+}
+
 // Messages
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -138,6 +271,23 @@ pub struct InstantiateMsg {
     pub role_manager: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    // Denom given to non-zero legacy entry fees, which were stored without one
+    #[serde(default)]
+    pub entry_fee_denom: Option<String>,
+    // Legacy entries to convert in this call, defaults to DEFAULT_MIGRATE_LIMIT
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+// Progress of a migration that needed more than one call
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+struct MigrationState {
+    // Last TRIBE_META key checked for a legacy entry fee
+    tribe_meta_after: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -146,7 +296,7 @@ pub enum ExecuteMsg {
         metadata: String,
         admins: Vec<String>,
         join_type: JoinType,
-        entry_fee: Uint128,
+        entry_fee: Option<Coin>,
         nft_requirements: Vec<NFTRequirement>,
     },
     UpdateTribe {
@@ -157,7 +307,7 @@ pub enum ExecuteMsg {
     UpdateTribeConfig {
         tribe_id: u64,
         join_type: JoinType,
-        entry_fee: Uint128,
+        entry_fee: Option<Coin>,
         nft_requirements: Vec<NFTRequirement>,
     },
     JoinTribe {
@@ -180,7 +330,7 @@ pub enum ExecuteMsg {
     },
     JoinTribeWithCode {
         tribe_id: u64,
-        invite_code: Vec<u8>,
+        invite_code: String,
     },
     CreateInviteCode {
         tribe_id: u64,
@@ -197,6 +347,8 @@ pub enum ExecuteMsg {
     },
     ExecuteMerge {
         merge_request_id: u64,
+        // Source members to move in this call
+        limit: Option<u32>,
     },
     RevokeInviteCode {
         tribe_id: u64,
@@ -205,6 +357,33 @@ pub enum ExecuteMsg {
     CancelMerge {
         merge_request_id: u64,
     },
+    DepositTreasury {
+        tribe_id: u64,
+    },
+    WithdrawTreasury {
+        tribe_id: u64,
+        amount: Coin,
+        recipient: String,
+    },
+    UpdateProtocolFee {
+        fee_bps: u16,
+        fee_collector: Option<String>,
+    },
+    // Sent by a session key registered in role_manager, executing `msg` as `owner`
+    ExecuteOnBehalf {
+        owner: String,
+        msg: Box<ExecuteMsg>,
+    },
+    // Hook notification sent by role_manager when a role is granted or removed
+    RoleChanged {
+        user: String,
+        role: String,
+        scope: Option<String>,
+        granted: bool,
+    },
+    // Relayed by role_manager's PauseAll / UnpauseAll
+    Pause {},
+    Unpause {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -243,6 +422,29 @@ pub enum QueryMsg {
     GetTribeDetails {
         tribe_id: u64,
     },
+    GetTreasury {
+        tribe_id: u64,
+    },
+    GetTreasuryHistory {
+        tribe_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    GetProtocolFee {},
+    IsPaused {},
+    // Members ordered by join time; without a status, lists everyone counted as a member.
+    // `start_after` is the (joined_at, member) of the last entry on the previous page
+    ListMembers {
+        tribe_id: u64,
+        status: Option<MemberStatus>,
+        start_after: Option<(u64, String)>,
+        limit: Option<u32>,
+    },
+    ListPendingRequests {
+        tribe_id: u64,
+        start_after: Option<(u64, String)>,
+        limit: Option<u32>,
+    },
 }
 
 // Query responses
@@ -266,6 +468,27 @@ pub struct MemberStatusResponse {
     pub status: MemberStatus,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryResponse {
+    pub balances: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MembersResponse {
+    pub members: Vec<TribeMember>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TreasuryHistoryResponse {
+    pub entries: Vec<TreasuryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProtocolFeeResponse {
+    pub fee_bps: u16,
+    pub fee_collector: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TribeConfigViewResponse {
     pub config: TribeConfigView,
@@ -302,6 +525,24 @@ pub enum RoleManagerQuery {
         user: String,
         role: String,
     },
+    HasScopedRole {
+        user: String,
+        role: String,
+        scope: String,
+    },
+    CanPerform {
+        user: String,
+        action: String,
+        scope: Option<String>,
+    },
+    IsBlocked {
+        user: String,
+    },
+    IsSessionAuthorized {
+        owner: String,
+        session: String,
+        action: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -353,6 +594,8 @@ pub fn instantiate(
         role_manager,
         next_tribe_id: 0,
         next_merge_request_id: 0,
+        protocol_fee_bps: 0,
+        fee_collector: None,
     };
     
     CONFIG.save(deps.storage, &config)?;
@@ -362,12 +605,219 @@ pub fn instantiate(
         .add_attribute("owner", info.sender))
 }
 
+// Moves membership from the per-field maps into `members()`, recomputes counts,
+// converts legacy entry fees and rekeys invite codes by (tribe, hash).
+// Works through at most `limit` legacy entries per call so large states can be
+// migrated across several transactions; execute stays closed until `completed`.
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let mut budget = msg.limit.unwrap_or(DEFAULT_MIGRATE_LIMIT) as usize;
+    if budget == 0 {
+        return Err(cosmwasm_std::StdError::generic_err("limit must be greater than 0"));
+    }
+    let mut state = MIGRATION.may_load(deps.storage)?.unwrap_or_default();
+    
+    let migrated = migrate_members(deps.storage, env.block.time.seconds(), &mut budget)?;
+    let migrated_tribes = migrate_tribe_metas(deps.storage, &msg, &mut state, &mut budget)?;
+    let migrated_invite_codes = migrate_invite_codes(deps.storage, &mut budget)?;
+    
+    let legacy_user_tribes = LEGACY_USER_TRIBES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(budget)
+        .collect::<StdResult<Vec<_>>>()?;
+    budget -= legacy_user_tribes.len();
+    for addr in legacy_user_tribes {
+        LEGACY_USER_TRIBES.remove(deps.storage, &addr);
+    }
+    
+    // A phase only stops early by spending the whole budget
+    let completed = budget > 0;
+    if completed {
+        MIGRATION.remove(deps.storage);
+        MIGRATION_RECOUNTED.clear(deps.storage);
+    } else {
+        MIGRATION.save(deps.storage, &state)?;
+    }
+    
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("migrated_members", migrated.to_string())
+        .add_attribute("migrated_tribes", migrated_tribes.to_string())
+        .add_attribute("migrated_invite_codes", migrated_invite_codes.to_string())
+        .add_attribute("completed", completed.to_string()))
+}
+
+// Older deployments kept membership in up to three maps; MEMBER_STATUS is the most
+// complete, then TRIBE_MEMBERS (pending requests), then the bare IS_MEMBER flags
+fn migrate_members(storage: &mut dyn Storage, now: u64, budget: &mut usize) -> StdResult<u64> {
+    let mut migrated = 0u64;
+    
+    let legacy = LEGACY_MEMBER_STATUS
+        .range(storage, None, None, Order::Ascending)
+        .take(*budget)
+        .collect::<StdResult<Vec<_>>>()?;
+    *budget -= legacy.len();
+    for (key, status) in legacy {
+        let (tribe_id, addr) = parse_legacy_member_key(&key)?;
+        
+        // Only pending requests were ever written to the old TRIBE_MEMBERS map;
+        // everyone else joins at migration time, in address order
+        let joined_at = LEGACY_TRIBE_MEMBERS
+            .may_load(storage, (u64_to_key(tribe_id), &addr))?
+            .map(|member| member.joined_at)
+            .filter(|joined_at| *joined_at != 0)
+            .unwrap_or(now);
+        migrate_member(storage, tribe_id, addr, status, joined_at)?;
+        migrated += 1;
+    }
+    
+    // Entries left in TRIBE_MEMBERS had no status entry; their own status stands
+    let legacy = LEGACY_TRIBE_MEMBERS
+        .range(storage, None, None, Order::Ascending)
+        .take(*budget)
+        .collect::<StdResult<Vec<_>>>()?;
+    *budget -= legacy.len();
+    for ((tribe_key, addr), member) in legacy {
+        let tribe_id = <[u8; 8]>::try_from(tribe_key.as_slice())
+            .map(u64::from_be_bytes)
+            .map_err(|_| cosmwasm_std::StdError::generic_err(format!("Invalid member key: {}:{}", hex::encode(&tribe_key), addr)))?;
+        let joined_at = if member.joined_at != 0 { member.joined_at } else { now };
+        migrate_member(storage, tribe_id, addr, member.status, joined_at)?;
+        migrated += 1;
+    }
+    
+    // Entries left in IS_MEMBER only say whether the address was a member
+    let legacy = LEGACY_IS_MEMBER
+        .range(storage, None, None, Order::Ascending)
+        .take(*budget)
+        .collect::<StdResult<Vec<_>>>()?;
+    *budget -= legacy.len();
+    for (key, is_member) in legacy {
+        let (tribe_id, addr) = parse_legacy_member_key(&key)?;
+        if is_member {
+            migrate_member(storage, tribe_id, addr, MemberStatus::ACTIVE, now)?;
+            migrated += 1;
+        } else {
+            LEGACY_IS_MEMBER.remove(storage, &key);
+        }
+    }
+    Ok(migrated)
+}
+
+fn parse_legacy_member_key(key: &str) -> StdResult<(u64, Addr)> {
+    let (tribe_id, addr) = key
+        .split_once(':')
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err(format!("Invalid member key: {}", key)))?;
+    let tribe_id: u64 = tribe_id
+        .parse()
+        .map_err(|_| cosmwasm_std::StdError::generic_err(format!("Invalid member key: {}", key)))?;
+    Ok((tribe_id, Addr::unchecked(addr)))
+}
+
+// Saves one legacy member into `members()` and drops it from every legacy map
+fn migrate_member(storage: &mut dyn Storage, tribe_id: u64, addr: Addr, status: MemberStatus, joined_at: u64) -> StdResult<()> {
+    // The old count drifted, so each tribe is recounted from its first migrated member
+    if !MIGRATION_RECOUNTED.has(storage, tribe_id) {
+        MEMBER_COUNT.remove(storage, &tribe_id.to_string());
+        MIGRATION_RECOUNTED.save(storage, tribe_id, &())?;
+    }
+    
+    let is_member = is_member_status(&status);
+    let member = TribeMember {
+        tribe_id,
+        member: addr.clone(),
+        status,
+        joined_at,
+    };
+    members().save(storage, (tribe_id, &addr), &member)?;
+    update_member_count(storage, tribe_id, false, is_member)?;
+    
+    let key = format!("{}:{}", tribe_id, addr);
+    LEGACY_MEMBER_STATUS.remove(storage, &key);
+    LEGACY_IS_MEMBER.remove(storage, &key);
+    LEGACY_TRIBE_MEMBERS.remove(storage, (u64_to_key(tribe_id), &addr));
+    Ok(())
+}
+
+// A zero fee meant a free tribe; other amounts get the agreed denom
+fn migrate_tribe_metas(
+    storage: &mut dyn Storage,
+    msg: &MigrateMsg,
+    state: &mut MigrationState,
+    budget: &mut usize,
+) -> StdResult<u64> {
+    let start = state.tribe_meta_after.as_deref().map(Bound::exclusive);
+    let metas = STORED_TRIBE_META
+        .range(storage, start, None, Order::Ascending)
+        .take(*budget)
+        .collect::<StdResult<Vec<_>>>()?;
+    *budget -= metas.len();
+    
+    let mut migrated = 0u64;
+    for (key, meta) in metas {
+        if let StoredTribeMeta::Legacy(legacy) = meta {
+            let entry_fee = if legacy.entry_fee.is_zero() {
+                None
+            } else {
+                let denom = msg.entry_fee_denom.clone()
+                    .ok_or_else(|| cosmwasm_std::StdError::generic_err("entry_fee_denom is required to convert legacy entry fees"))?;
+                Some(Coin { denom, amount: legacy.entry_fee })
+            };
+            let meta = TribeMeta {
+                name: legacy.name,
+                metadata: legacy.metadata,
+                admin: legacy.admin,
+                whitelist: legacy.whitelist,
+                join_type: legacy.join_type,
+                entry_fee,
+                nft_requirements: legacy.nft_requirements,
+                can_merge: legacy.can_merge,
+                is_active: legacy.is_active,
+                member_count: legacy.member_count,
+                merged_into: None,
+            };
+            TRIBE_META.save(storage, &key, &meta)?;
+            migrated += 1;
+        }
+        state.tribe_meta_after = Some(key);
+    }
+    Ok(migrated)
+}
+
+fn migrate_invite_codes(storage: &mut dyn Storage, budget: &mut usize) -> StdResult<u64> {
+    let legacy_codes = LEGACY_INVITE_CODE
+        .range(storage, None, None, Order::Ascending)
+        .take(*budget)
+        .collect::<StdResult<Vec<_>>>()?;
+    *budget -= legacy_codes.len();
+    
+    let mut migrated = 0u64;
+    for (key, invite_code) in legacy_codes {
+        let tribe_id: u64 = key
+            .split_once(':')
+            .and_then(|(tribe_id, _)| tribe_id.parse().ok())
+            .ok_or_else(|| cosmwasm_std::StdError::generic_err(format!("Invalid invite code key: {}", key)))?;
+        INVITE_CODE.save(storage, (tribe_id, &invite_code.code_hash), &invite_code)?;
+        LEGACY_INVITE_CODE.remove(storage, &key);
+        migrated += 1;
+    }
+    Ok(migrated)
+}
+
 pub fn execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
+    // Only pause control and role_manager hooks get through while paused
+    let allowed_while_paused = matches!(msg, ExecuteMsg::Pause {} | ExecuteMsg::Unpause {} | ExecuteMsg::RoleChanged { .. });
+    if !allowed_while_paused && PAUSED.may_load(deps.storage)?.unwrap_or(false) {
+        return Err(cosmwasm_std::StdError::generic_err("Contract is paused"));
+    }
+    if MIGRATION.exists(deps.storage) {
+        return Err(cosmwasm_std::StdError::generic_err("Migration in progress"));
+    }
+    
     match msg {
         ExecuteMsg::CreateTribe {
             name,
@@ -403,9 +853,16 @@ pub fn execute(
         ExecuteMsg::CreateInviteCode { tribe_id, code, max_uses, expiry_time } => createInviteCode(deps, env, info, tribe_id, code, max_uses, expiry_time),
         ExecuteMsg::RequestMerge { source_tribe_id, target_tribe_id } => requestMerge(deps, env, info, source_tribe_id, target_tribe_id),
         ExecuteMsg::ApproveMerge { merge_request_id } => approveMerge(deps, env, info, merge_request_id),
-        ExecuteMsg::ExecuteMerge { merge_request_id } => executeMerge(deps, env, info, merge_request_id),
+        ExecuteMsg::ExecuteMerge { merge_request_id, limit } => executeMerge(deps, env, info, merge_request_id, limit),
         ExecuteMsg::RevokeInviteCode { tribe_id, code } => revokeInviteCode(deps, env, info, tribe_id, code),
         ExecuteMsg::CancelMerge { merge_request_id } => cancelMerge(deps, env, info, merge_request_id),
+        ExecuteMsg::DepositTreasury { tribe_id } => depositTreasury(deps, env, info, tribe_id),
+        ExecuteMsg::WithdrawTreasury { tribe_id, amount, recipient } => withdrawTreasury(deps, env, info, tribe_id, amount, recipient),
+        ExecuteMsg::UpdateProtocolFee { fee_bps, fee_collector } => updateProtocolFee(deps, info, fee_bps, fee_collector),
+        ExecuteMsg::ExecuteOnBehalf { owner, msg } => executeOnBehalf(deps, env, info, owner, *msg),
+        ExecuteMsg::RoleChanged { user, role, scope, granted } => roleChanged(deps, info, user, role, scope, granted),
+        ExecuteMsg::Pause {} => setPaused(deps, info, true),
+        ExecuteMsg::Unpause {} => setPaused(deps, info, false),
         _ => Err(cosmwasm_std::StdError::generic_err("Unsupported operation")),
     }
 }
@@ -423,11 +880,32 @@ fn is_tribe_admin(
         return Ok(true);
     }
     
-    // Check if addr has MODERATOR_ROLE
+    // Check if addr may moderate this tribe per role_manager's permission matrix
     let config = get_config(storage)?;
-    let msg = to_json_binary(&RoleManagerQuery::HasRole {
+    let msg = to_json_binary(&RoleManagerQuery::CanPerform {
+        user: addr.to_string(),
+        action: ACTION_MODERATE.to_string(),
+        scope: Some(tribe_scope(tribe_id)),
+    })?;
+    
+    let query = cosmwasm_std::WasmQuery::Smart {
+        contract_addr: config.role_manager.to_string(),
+        msg,
+    };
+    
+    match deps.querier.query::<BoolResponse>(&query.into()) {
+        Ok(response) => Ok(response.result),
+        Err(_) => Ok(false),
+    }
+}
+
+// Helper function to check a platform-wide action in role_manager's permission matrix
+fn can_perform(deps: Deps, addr: &Addr, action: &str) -> StdResult<bool> {
+    let config = get_config(deps.storage)?;
+    let msg = to_json_binary(&RoleManagerQuery::CanPerform {
         user: addr.to_string(),
-        role: "MODERATOR_ROLE".to_string(),
+        action: action.to_string(),
+        scope: None,
     })?;
     
     let query = cosmwasm_std::WasmQuery::Smart {
@@ -435,12 +913,157 @@ fn is_tribe_admin(
         msg,
     };
     
-    match deps.querier.query::<RoleResponse>(&query.into()) {
-        Ok(response) => Ok(response.has_role),
+    match deps.querier.query::<BoolResponse>(&query.into()) {
+        Ok(response) => Ok(response.result),
         Err(_) => Ok(false),
     }
 }
 
+// Helper function to check the platform-wide blocklist in role_manager; an unanswered query counts as blocked
+fn is_blocked(deps: Deps, addr: &Addr) -> StdResult<bool> {
+    let config = get_config(deps.storage)?;
+    let msg = to_json_binary(&RoleManagerQuery::IsBlocked {
+        user: addr.to_string(),
+    })?;
+    
+    let query = cosmwasm_std::WasmQuery::Smart {
+        contract_addr: config.role_manager.to_string(),
+        msg,
+    };
+    
+    match deps.querier.query::<BoolResponse>(&query.into()) {
+        Ok(response) => Ok(response.result),
+        Err(_) => Ok(true),
+    }
+}
+
+// Check that `session` holds a live role_manager session key of `owner` for `action`
+fn is_session_authorized(deps: Deps, owner: &Addr, session: &Addr, action: &str) -> StdResult<bool> {
+    let config = get_config(deps.storage)?;
+    let msg = to_json_binary(&RoleManagerQuery::IsSessionAuthorized {
+        owner: owner.to_string(),
+        session: session.to_string(),
+        action: action.to_string(),
+    })?;
+    
+    let query = cosmwasm_std::WasmQuery::Smart {
+        contract_addr: config.role_manager.to_string(),
+        msg,
+    };
+    
+    match deps.querier.query::<BoolResponse>(&query.into()) {
+        Ok(response) => Ok(response.result),
+        Err(_) => Ok(false),
+    }
+}
+
+// Session action covering `msg`; only membership requests can be delegated
+fn session_action(msg: &ExecuteMsg) -> Option<&'static str> {
+    match msg {
+        ExecuteMsg::JoinTribe { .. }
+        | ExecuteMsg::RequestToJoinTribe { .. }
+        | ExecuteMsg::JoinTribeWithCode { .. } => Some(SESSION_ACTION_JOIN),
+        _ => None,
+    }
+}
+
+// Fee-charging tribes need a non-zero fee; free tribes use None
+fn validate_entry_fee_config(entry_fee: &Option<Coin>) -> StdResult<()> {
+    match entry_fee {
+        Some(fee) if fee.amount.is_zero() => Err(cosmwasm_std::StdError::generic_err("Entry fee must be non-zero")),
+        _ => Ok(()),
+    }
+}
+
+// The exact entry fee must be attached: one coin, right denom, right amount
+fn validate_entry_fee(entry_fee: &Option<Coin>, funds: &[Coin]) -> StdResult<()> {
+    match (entry_fee, funds) {
+        (None, []) => Ok(()),
+        (None, _) => Err(cosmwasm_std::StdError::generic_err("Tribe has no entry fee")),
+        (Some(fee), [paid]) if paid == fee => Ok(()),
+        (Some(fee), _) => Err(cosmwasm_std::StdError::generic_err(format!("Entry fee must be paid as exactly {}", fee))),
+    }
+}
+
+// Credits incoming funds to a tribe's treasury minus the protocol fee, returning the fee transfer
+fn credit_treasury(
+    storage: &mut dyn Storage,
+    env: &Env,
+    tribe_id: u64,
+    amount: &Coin,
+    kind: TreasuryEntryKind,
+    counterparty: &Addr,
+) -> StdResult<Vec<CosmosMsg>> {
+    let config = get_config(storage)?;
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut protocol_fee = Uint128::zero();
+    if let Some(fee_collector) = &config.fee_collector {
+        protocol_fee = amount.amount.multiply_ratio(config.protocol_fee_bps, 10_000u128);
+        if !protocol_fee.is_zero() {
+            msgs.push(BankMsg::Send {
+                to_address: fee_collector.to_string(),
+                amount: vec![Coin { denom: amount.denom.clone(), amount: protocol_fee }],
+            }
+            .into());
+        }
+    }
+    
+    TREASURY.update(storage, (tribe_id, amount.denom.as_str()), |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_add(amount.amount - protocol_fee)?)
+    })?;
+    record_treasury_entry(storage, env, tribe_id, amount, kind, protocol_fee, counterparty)?;
+    Ok(msgs)
+}
+
+fn record_treasury_entry(
+    storage: &mut dyn Storage,
+    env: &Env,
+    tribe_id: u64,
+    amount: &Coin,
+    kind: TreasuryEntryKind,
+    protocol_fee: Uint128,
+    counterparty: &Addr,
+) -> StdResult<()> {
+    let id = NEXT_TREASURY_ENTRY_ID.may_load(storage)?.unwrap_or(0);
+    NEXT_TREASURY_ENTRY_ID.save(storage, &(id + 1))?;
+    
+    let entry = TreasuryEntry {
+        id,
+        kind,
+        amount: amount.clone(),
+        protocol_fee,
+        counterparty: counterparty.clone(),
+        timestamp: env.block.time.seconds(),
+    };
+    TREASURY_HISTORY.save(storage, (tribe_id, id), &entry)
+}
+
+fn refund_escrowed_fee(storage: &mut dyn Storage, tribe_id: u64, member: &Addr) -> StdResult<Vec<CosmosMsg>> {
+    let Some(fee) = ESCROWED_FEES.may_load(storage, (tribe_id, member))? else {
+        return Ok(vec![]);
+    };
+    ESCROWED_FEES.remove(storage, (tribe_id, member));
+    Ok(vec![BankMsg::Send {
+        to_address: member.to_string(),
+        amount: vec![fee],
+    }
+    .into()])
+}
+
+fn format_entry_fee(entry_fee: &Option<Coin>) -> String {
+    entry_fee.as_ref().map_or_else(|| "none".to_string(), Coin::to_string)
+}
+
+// Invite codes are stored and looked up by the SHA-256 of their text
+fn hash_invite_code(code: &str) -> Vec<u8> {
+    Sha256::digest(code.as_bytes()).to_vec()
+}
+
+// Scope used for tribe-specific grants in role_manager
+pub fn tribe_scope(tribe_id: u64) -> String {
+    format!("tribe:{}", tribe_id)
+}
+
 fn get_config(storage: &dyn Storage) -> StdResult<Config> {
     CONFIG.load(storage)
 }
@@ -458,7 +1081,7 @@ pub fn createTribe(
     metadata: String,
     admins: Vec<String>,
     join_type: JoinType,
-    entry_fee: Uint128,
+    entry_fee: Option<Coin>,
     nft_requirements: Vec<NFTRequirement>,
 ) -> Result<Response, ContractError> {
     // Check if whitelisted
@@ -466,6 +1089,12 @@ pub fn createTribe(
         return Err(ContractError::Unauthorized {});
     }
     
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Blocked {});
+    }
+    
+    validate_entry_fee_config(&entry_fee)?;
+    
     // Validate NFT requirements if join type is NFT_REQUIRED
     if join_type == JoinType::NFTRequired {
         if nft_requirements.is_empty() {
@@ -497,21 +1126,14 @@ pub fn createTribe(
         can_merge: true,
         is_active: true,
         member_count: None,
+        merged_into: None,
     };
     
     // Save tribe metadata
     TRIBE_META.save(deps.storage, &tribe_id.to_string(), &tribe_meta)?;
     
     // Add creator as active member
-    let member_status_key = format!("{}:{}", tribe_id, info.sender);
-    MEMBER_STATUS.save(deps.storage, &member_status_key, &MemberStatus::ACTIVE)?;
-    
-    // Set member count to 1
-    MEMBER_COUNT.save(deps.storage, &tribe_id.to_string(), &1u64)?;
-    
-    // Mark creator as member
-    let is_member_key = format!("{}:{}", tribe_id, info.sender);
-    IS_MEMBER.save(deps.storage, &is_member_key, &true)?;
+    set_member_status(deps.storage, env.block.time.seconds(), tribe_id, &info.sender, MemberStatus::ACTIVE)?;
     
     Ok(Response::new()
         .add_attribute("action", "create_tribe")
@@ -561,7 +1183,7 @@ pub fn updateTribe(
         .add_attribute("action", "update_tribe")
         .add_attribute("tribe_id", tribe_id.to_string())
         .add_attribute("join_type", format!("{:?}", tribe_meta.join_type))
-        .add_attribute("entry_fee", tribe_meta.entry_fee.to_string()))
+        .add_attribute("entry_fee", format_entry_fee(&tribe_meta.entry_fee)))
 }
 
 pub fn updateTribeConfig(
@@ -570,7 +1192,7 @@ pub fn updateTribeConfig(
     info: MessageInfo,
     tribe_id: u64,
     join_type: JoinType,
-    entry_fee: Uint128,
+    entry_fee: Option<Coin>,
     nft_requirements: Vec<NFTRequirement>,
 ) -> StdResult<Response> {
     // Check if sender is tribe admin
@@ -579,9 +1201,11 @@ pub fn updateTribeConfig(
     // Get tribe metadata
     let mut tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
     
+    validate_entry_fee_config(&entry_fee)?;
+    
     // Update config
     tribe_meta.join_type = join_type.clone();
-    tribe_meta.entry_fee = entry_fee;
+    tribe_meta.entry_fee = entry_fee.clone();
     tribe_meta.nft_requirements = nft_requirements;
     
     // Save updated tribe metadata
@@ -591,7 +1215,7 @@ pub fn updateTribeConfig(
         .add_attribute("action", "update_tribe_config")
         .add_attribute("tribe_id", tribe_id.to_string())
         .add_attribute("join_type", format!("{:?}", join_type))
-        .add_attribute("entry_fee", entry_fee.to_string()))
+        .add_attribute("entry_fee", format_entry_fee(&entry_fee)))
 }
 
 pub fn joinTribe(
@@ -606,18 +1230,16 @@ pub fn joinTribe(
         return Err(ContractError::CustomError { message: "Invalid tribe ID".to_string() });
     }
     
-    // Check if user is already a member
-    let is_member_key = format!("{}:{}", tribe_id, info.sender);
-    if IS_MEMBER.may_load(deps.storage, &is_member_key)?.is_some() {
-        return Err(ContractError::CustomError { message: "Already a member".to_string() });
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(ContractError::Blocked {});
     }
     
-    // Check if banned
-    let member_status_key = format!("{}:{}", tribe_id, info.sender);
-    if let Some(status) = MEMBER_STATUS.may_load(deps.storage, &member_status_key)? {
-        if status == MemberStatus::BANNED {
-            return Err(ContractError::CustomError { message: "User is banned".to_string() });
-        }
+    // Check existing membership
+    match member_status(deps.storage, tribe_id, &info.sender)? {
+        MemberStatus::BANNED => return Err(ContractError::CustomError { message: "User is banned".to_string() }),
+        MemberStatus::PENDING => return Err(ContractError::CustomError { message: "Request already pending".to_string() }),
+        status if is_member_status(&status) => return Err(ContractError::CustomError { message: "Already a member".to_string() }),
+        _ => {}
     }
     
     // Get tribe metadata
@@ -642,15 +1264,15 @@ pub fn joinTribe(
         }
     }
     
-    // Add as active member
-    MEMBER_STATUS.save(deps.storage, &member_status_key, &MemberStatus::ACTIVE)?;
-    
-    // Mark as member
-    IS_MEMBER.save(deps.storage, &is_member_key, &true)?;
+    // Collect the entry fee
+    validate_entry_fee(&tribe_meta.entry_fee, &info.funds)?;
+    let mut fee_msgs = vec![];
+    if let Some(fee) = &tribe_meta.entry_fee {
+        fee_msgs = credit_treasury(deps.storage, &env, tribe_id, fee, TreasuryEntryKind::EntryFee, &info.sender)?;
+    }
     
-    // Update member count
-    let current_count = MEMBER_COUNT.may_load(deps.storage, &tribe_id.to_string())?.unwrap_or(0);
-    MEMBER_COUNT.save(deps.storage, &tribe_id.to_string(), &(current_count + 1))?;
+    // Add as active member
+    set_member_status(deps.storage, env.block.time.seconds(), tribe_id, &info.sender, MemberStatus::ACTIVE)?;
     
     // Add to whitelist
     let mut tribe_meta = tribe_meta;
@@ -658,6 +1280,7 @@ pub fn joinTribe(
     TRIBE_META.save(deps.storage, &tribe_id.to_string(), &tribe_meta)?;
     
     Ok(Response::new()
+        .add_messages(fee_msgs)
         .add_attribute("action", "join_tribe")
         .add_attribute("tribe_id", tribe_id.to_string())
         .add_attribute("member", info.sender.to_string()))
@@ -800,7 +1423,7 @@ fn _validateSingleNFTRequirement(
 
 pub fn requestToJoinTribe(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     tribe_id: u64,
 ) -> StdResult<Response> {
@@ -808,50 +1431,42 @@ pub fn requestToJoinTribe(
     let tribe_meta = TRIBE_META.may_load(deps.storage, &tribe_id.to_string())?
         .ok_or_else(|| cosmwasm_std::StdError::generic_err("Tribe not found"))?;
     
+    // Check if tribe is active
+    if !tribe_meta.is_active {
+        return Err(cosmwasm_std::StdError::generic_err("Tribe not active"));
+    }
+    
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
+    }
+    
     // Check join type
     if tribe_meta.join_type != JoinType::PRIVATE {
         return Err(cosmwasm_std::StdError::generic_err("Tribe is not private. Use joinTribe"));
     }
     
-    // Check if address is already a member
-    if is_tribe_member(deps.as_ref(), tribe_id, &info.sender)? {
-        return Err(cosmwasm_std::StdError::generic_err("Already a member"));
+    // Check if address is already a member or has a request open
+    match member_status(deps.storage, tribe_id, &info.sender)? {
+        MemberStatus::PENDING => {
+            return Err(cosmwasm_std::StdError::generic_err("Request already pending"));
+        },
+        MemberStatus::BANNED => {
+            return Err(cosmwasm_std::StdError::generic_err("User is banned from tribe"));
+        },
+        status if is_member_status(&status) => {
+            return Err(cosmwasm_std::StdError::generic_err("Already a member"));
+        },
+        _ => {}
     }
     
-    // Check if request already exists
-    let member_key = format!("{}:{}", tribe_id, info.sender);
-    let member_status = MEMBER_STATUS.may_load(deps.storage, &member_key)?;
-    
-    if let Some(status) = member_status {
-        match status {
-            MemberStatus::PENDING => {
-                return Err(cosmwasm_std::StdError::generic_err("Request already pending"));
-            },
-            MemberStatus::NONE => {
-                return Err(cosmwasm_std::StdError::generic_err("Request was previously rejected"));
-            },
-            MemberStatus::BANNED => {
-                return Err(cosmwasm_std::StdError::generic_err("User is banned from tribe"));
-            },
-            _ => { 
-                // Continue with joining
-            }
-        }
+    // Hold the entry fee until an admin decides on the request
+    validate_entry_fee(&tribe_meta.entry_fee, &info.funds)?;
+    if let Some(fee) = &tribe_meta.entry_fee {
+        ESCROWED_FEES.save(deps.storage, (tribe_id, &info.sender), fee)?;
     }
     
-    // Create pending membership request
-    let member = TribeMember {
-        tribe_id,
-        member: info.sender.clone(),
-        status: MemberStatus::PENDING,
-        joined_at: 0, // Will be set when approved
-    };
-    
-    // Save to tribe members
-    TRIBE_MEMBERS.save(deps.storage, (u64_to_key(tribe_id), &info.sender), &member)?;
-    
-    // Set member status
-    MEMBER_STATUS.save(deps.storage, &member_key, &MemberStatus::PENDING)?;
+    // Create pending membership request; joined_at records the request time until approval
+    set_member_status(deps.storage, env.block.time.seconds(), tribe_id, &info.sender, MemberStatus::PENDING)?;
     
     Ok(Response::new()
         .add_attribute("action", "request_to_join_tribe")
@@ -861,7 +1476,7 @@ pub fn requestToJoinTribe(
 
 pub fn approveMember(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     tribe_id: u64,
     member: String,
@@ -869,11 +1484,16 @@ pub fn approveMember(
     // Check if sender is tribe admin
     only_tribe_admin!(deps, info, tribe_id);
     
+    // A tribe being merged away takes no new members
+    let tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
+    if !tribe_meta.is_active {
+        return Err(cosmwasm_std::StdError::generic_err("Tribe not active"));
+    }
+    
     let member_addr = deps.api.addr_validate(&member)?;
     
     // Get member status
-    let member_status_key = format!("{}:{}", tribe_id, member);
-    let member_status = MEMBER_STATUS.may_load(deps.storage, &member_status_key)?;
+    let member_status = members().may_load(deps.storage, (tribe_id, &member_addr))?.map(|member| member.status);
     
     if let Some(status) = member_status {
         if status == MemberStatus::BANNED {
@@ -887,10 +1507,23 @@ pub fn approveMember(
         return Err(cosmwasm_std::StdError::generic_err("User not found"));
     }
     
+    // The user may have been blocked after requesting to join
+    if is_blocked(deps.as_ref(), &member_addr)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
+    }
+    
+    // Release the escrowed entry fee to the tribe
+    let mut fee_msgs = vec![];
+    if let Some(fee) = ESCROWED_FEES.may_load(deps.storage, (tribe_id, &member_addr))? {
+        fee_msgs = credit_treasury(deps.storage, &env, tribe_id, &fee, TreasuryEntryKind::EntryFee, &member_addr)?;
+        ESCROWED_FEES.remove(deps.storage, (tribe_id, &member_addr));
+    }
+    
     // Update member status to active
-    MEMBER_STATUS.save(deps.storage, &member_status_key, &MemberStatus::ACTIVE)?;
+    set_member_status(deps.storage, env.block.time.seconds(), tribe_id, &member_addr, MemberStatus::ACTIVE)?;
     
     Ok(Response::new()
+        .add_messages(fee_msgs)
         .add_attribute("action", "approve_member")
         .add_attribute("tribe_id", tribe_id.to_string())
         .add_attribute("member", member_addr.to_string()))
@@ -909,8 +1542,7 @@ pub fn rejectMember(
     let member_addr = deps.api.addr_validate(&member)?;
     
     // Get member status
-    let member_status_key = format!("{}:{}", tribe_id, member);
-    let member_status = MEMBER_STATUS.may_load(deps.storage, &member_status_key)?;
+    let member_status = members().may_load(deps.storage, (tribe_id, &member_addr))?.map(|member| member.status);
     
     if let Some(status) = member_status {
         if status == MemberStatus::BANNED {
@@ -925,9 +1557,10 @@ pub fn rejectMember(
     }
     
     // Remove member
-    MEMBER_STATUS.remove(deps.storage, &member_status_key);
+    remove_member(deps.storage, tribe_id, &member_addr)?;
     
     Ok(Response::new()
+        .add_messages(refund_escrowed_fee(deps.storage, tribe_id, &member_addr)?)
         .add_attribute("action", "reject_member")
         .add_attribute("tribe_id", tribe_id.to_string())
         .add_attribute("member", member_addr.to_string()))
@@ -935,7 +1568,7 @@ pub fn rejectMember(
 
 pub fn banMember(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     tribe_id: u64,
     member: String,
@@ -946,8 +1579,7 @@ pub fn banMember(
     let member_addr = deps.api.addr_validate(&member)?;
     
     // Get member status
-    let member_status_key = format!("{}:{}", tribe_id, member);
-    let member_status = MEMBER_STATUS.may_load(deps.storage, &member_status_key)?;
+    let member_status = members().may_load(deps.storage, (tribe_id, &member_addr))?.map(|member| member.status);
     
     if let Some(status) = member_status {
         if status == MemberStatus::BANNED {
@@ -958,9 +1590,11 @@ pub fn banMember(
     }
     
     // Update member status to banned
-    MEMBER_STATUS.save(deps.storage, &member_status_key, &MemberStatus::BANNED)?;
+    set_member_status(deps.storage, env.block.time.seconds(), tribe_id, &member_addr, MemberStatus::BANNED)?;
     
+    // A pending request's fee goes back to the applicant
     Ok(Response::new()
+        .add_messages(refund_escrowed_fee(deps.storage, tribe_id, &member_addr)?)
         .add_attribute("action", "ban_member")
         .add_attribute("tribe_id", tribe_id.to_string())
         .add_attribute("member", member_addr.to_string()))
@@ -968,10 +1602,10 @@ pub fn banMember(
 
 pub fn joinTribeWithCode(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     tribe_id: u64,
-    invite_code: Vec<u8>,
+    invite_code: String,
 ) -> StdResult<Response> {
     // Validate tribe ID
     let config = get_config(deps.storage)?;
@@ -979,18 +1613,16 @@ pub fn joinTribeWithCode(
         return Err(cosmwasm_std::StdError::generic_err("Invalid tribe ID"));
     }
     
-    // Check if already a member
-    let is_member_key = format!("{}:{}", tribe_id, info.sender);
-    if IS_MEMBER.may_load(deps.storage, &is_member_key)?.is_some() {
-        return Err(cosmwasm_std::StdError::generic_err("Already a member"));
+    if is_blocked(deps.as_ref(), &info.sender)? {
+        return Err(cosmwasm_std::StdError::generic_err("Address is blocked"));
     }
     
-    // Check if banned
-    let member_status_key = format!("{}:{}", tribe_id, info.sender);
-    if let Some(status) = MEMBER_STATUS.may_load(deps.storage, &member_status_key)? {
-        if status == MemberStatus::BANNED {
-            return Err(cosmwasm_std::StdError::generic_err("User is banned"));
-        }
+    // Check existing membership
+    match member_status(deps.storage, tribe_id, &info.sender)? {
+        MemberStatus::BANNED => return Err(cosmwasm_std::StdError::generic_err("User is banned")),
+        MemberStatus::PENDING => return Err(cosmwasm_std::StdError::generic_err("Request already pending")),
+        status if is_member_status(&status) => return Err(cosmwasm_std::StdError::generic_err("Already a member")),
+        _ => {}
     }
     
     // Get tribe metadata
@@ -1006,34 +1638,36 @@ pub fn joinTribeWithCode(
         return Err(cosmwasm_std::StdError::generic_err("Tribe does not use invite codes"));
     }
     
-    // Validate invite code
-    let code_found = tribe_meta.whitelist.iter().any(|addr| {
-        if let Ok(a) = deps.api.addr_validate(&String::from_utf8_lossy(&invite_code)) {
-            return *addr == a;
-        }
-        false
-    });
-    
-    if !code_found {
-        return Err(cosmwasm_std::StdError::generic_err("Invalid invite code"));
+    // Redeem the invite code
+    let code_hash = hash_invite_code(&invite_code);
+    let mut code = INVITE_CODE.may_load(deps.storage, (tribe_id, &code_hash))?
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("Invalid invite code"))?;
+    if env.block.time.seconds() >= code.expiry_time {
+        return Err(cosmwasm_std::StdError::generic_err("Invite code expired"));
+    }
+    if code.used_count >= code.max_uses {
+        return Err(cosmwasm_std::StdError::generic_err("Invite code fully used"));
+    }
+    code.used_count += 1;
+    INVITE_CODE.save(deps.storage, (tribe_id, &code_hash), &code)?;
+    
+    // Collect the entry fee
+    validate_entry_fee(&tribe_meta.entry_fee, &info.funds)?;
+    let mut fee_msgs = vec![];
+    if let Some(fee) = &tribe_meta.entry_fee {
+        fee_msgs = credit_treasury(deps.storage, &env, tribe_id, fee, TreasuryEntryKind::EntryFee, &info.sender)?;
     }
     
     // Add as active member
-    MEMBER_STATUS.save(deps.storage, &member_status_key, &MemberStatus::ACTIVE)?;
+    set_member_status(deps.storage, env.block.time.seconds(), tribe_id, &info.sender, MemberStatus::ACTIVE)?;
     
-    // Mark as member
-    IS_MEMBER.save(deps.storage, &is_member_key, &true)?;
-    
-    // Increment member count
-    let current_count = MEMBER_COUNT.may_load(deps.storage, &tribe_id.to_string())?.unwrap_or(0);
-    MEMBER_COUNT.save(deps.storage, &tribe_id.to_string(), &(current_count + 1))?;
-    
-    // Update tribe whitelist
+    // Add to whitelist
     let mut tribe_meta = tribe_meta;
     tribe_meta.whitelist.push(info.sender.clone());
     TRIBE_META.save(deps.storage, &tribe_id.to_string(), &tribe_meta)?;
     
     Ok(Response::new()
+        .add_messages(fee_msgs)
         .add_attribute("action", "join_tribe_with_code")
         .add_attribute("tribe_id", tribe_id.to_string())
         .add_attribute("member", info.sender.to_string()))
@@ -1041,7 +1675,7 @@ pub fn joinTribeWithCode(
 
 pub fn createInviteCode(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     tribe_id: u64,
     code: String,
@@ -1051,20 +1685,24 @@ pub fn createInviteCode(
     // Check if sender is tribe admin
     only_tribe_admin!(deps, info, tribe_id);
     
-    // Get tribe metadata
-    let mut tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
+    if max_uses == 0 {
+        return Err(cosmwasm_std::StdError::generic_err("Invite code needs at least one use"));
+    }
+    if expiry_time <= env.block.time.seconds() {
+        return Err(cosmwasm_std::StdError::generic_err("Invite code expiry must be in the future"));
+    }
     
     // Create invite code struct
     let invite_code = InviteCode {
-        code_hash: Sha256::digest(&code.as_bytes()).to_vec(),
+        code_hash: hash_invite_code(&code),
         max_uses,
         used_count: 0,
         expiry_time,
     };
-
-    // Save the invite code with tribe_id prefixed key
-    let key = format!("{}:{}", tribe_id, hex::encode(&invite_code.code_hash));
-    INVITE_CODE.save(deps.storage, &key, &invite_code)?;
+    if INVITE_CODE.has(deps.storage, (tribe_id, &invite_code.code_hash)) {
+        return Err(cosmwasm_std::StdError::generic_err("Invite code already exists"));
+    }
+    INVITE_CODE.save(deps.storage, (tribe_id, &invite_code.code_hash), &invite_code)?;
     
     Ok(Response::new()
         .add_attribute("action", "create_invite_code")
@@ -1101,6 +1739,8 @@ pub fn requestMerge(
         target_tribe_id,
         request_time: current_time,
         approved: false,
+        in_progress: false,
+        moved_members: 0,
     };
     
     // Save merge request
@@ -1145,12 +1785,13 @@ pub fn approveMerge(
 
 pub fn executeMerge(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     merge_request_id: u64,
+    limit: Option<u32>,
 ) -> StdResult<Response> {
     // Get merge request
-    let merge_request = MERGE_REQUEST.load(deps.storage, &merge_request_id.to_string())?;
+    let mut merge_request = MERGE_REQUEST.load(deps.storage, &merge_request_id.to_string())?;
     
     // Validate merge request
     if !merge_request.approved {
@@ -1163,25 +1804,102 @@ pub fn executeMerge(
     // Get source and target tribe IDs
     let source_tribe_id = merge_request.source_tribe_id;
     let target_tribe_id = merge_request.target_tribe_id;
+    let now = env.block.time.seconds();
     
-    // Update source tribe
-    let mut source_tribe_meta = TRIBE_META.load(deps.storage, &source_tribe_id.to_string())?;
-    source_tribe_meta.is_active = false;
-    TRIBE_META.save(deps.storage, &source_tribe_id.to_string(), &source_tribe_meta)?;
+    // Members are only ever moved into an active tribe, on every chunk
+    if !TRIBE_META.load(deps.storage, &target_tribe_id.to_string())?.is_active {
+        return Err(cosmwasm_std::StdError::generic_err("Target tribe not active"));
+    }
     
-    // Update target tribe
-    let mut target_tribe_meta = TRIBE_META.load(deps.storage, &target_tribe_id.to_string())?;
-    target_tribe_meta.is_active = true;
-    TRIBE_META.save(deps.storage, &target_tribe_id.to_string(), &target_tribe_meta)?;
+    // First chunk: close the source tribe so no one joins mid-merge
+    if !merge_request.in_progress {
+        let mut source_tribe_meta = TRIBE_META.load(deps.storage, &source_tribe_id.to_string())?;
+        if !source_tribe_meta.is_active {
+            return Err(cosmwasm_std::StdError::generic_err("Source tribe not active"));
+        }
+        source_tribe_meta.is_active = false;
+        TRIBE_META.save(deps.storage, &source_tribe_id.to_string(), &source_tribe_meta)?;
+        merge_request.in_progress = true;
+    }
     
-    // Remove merge request
-    MERGE_REQUEST.remove(deps.storage, &merge_request_id.to_string());
+    // Processed source entries are removed, so each chunk starts from the front
+    let limit = limit.unwrap_or(DEFAULT_MERGE_CHUNK).min(MAX_MERGE_CHUNK) as usize;
+    let chunk = members()
+        .prefix(source_tribe_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, member)| member))
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    let mut msgs: Vec<CosmosMsg> = vec![];
+    let mut moved = 0u64;
+    for source_member in &chunk {
+        let addr = &source_member.member;
+        if is_member_status(&source_member.status) {
+            // Existing target members are kept as-is and target bans still apply
+            let target_status = member_status(deps.storage, target_tribe_id, addr)?;
+            if target_status != MemberStatus::BANNED && !is_member_status(&target_status) {
+                msgs.extend(refund_escrowed_fee(deps.storage, target_tribe_id, addr)?);
+                set_member_status(deps.storage, now, target_tribe_id, addr, MemberStatus::ACTIVE)?;
+                moved += 1;
+            }
+        }
+        // Open requests to the source tribe can no longer be approved
+        msgs.extend(refund_escrowed_fee(deps.storage, source_tribe_id, addr)?);
+        remove_member(deps.storage, source_tribe_id, addr)?;
+    }
+    merge_request.moved_members += moved;
+    
+    let completed = members()
+        .prefix(source_tribe_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .next()
+        .is_none();
+    if completed {
+        transfer_treasury(deps.storage, &env, source_tribe_id, target_tribe_id)?;
+        
+        let mut source_tribe_meta = TRIBE_META.load(deps.storage, &source_tribe_id.to_string())?;
+        source_tribe_meta.merged_into = Some(target_tribe_id);
+        TRIBE_META.save(deps.storage, &source_tribe_id.to_string(), &source_tribe_meta)?;
+        
+        // Remove merge request
+        MERGE_REQUEST.remove(deps.storage, &merge_request_id.to_string());
+    } else {
+        MERGE_REQUEST.save(deps.storage, &merge_request_id.to_string(), &merge_request)?;
+    }
     
     Ok(Response::new()
+        .add_messages(msgs)
         .add_attribute("action", "execute_merge")
         .add_attribute("merge_request_id", merge_request_id.to_string())
         .add_attribute("source_tribe_id", source_tribe_id.to_string())
-        .add_attribute("target_tribe_id", target_tribe_id.to_string()))
+        .add_attribute("target_tribe_id", target_tribe_id.to_string())
+        .add_attribute("moved_members", moved.to_string())
+        .add_attribute("total_moved_members", merge_request.moved_members.to_string())
+        .add_attribute("completed", completed.to_string()))
+}
+
+// Moves every balance of the source treasury to the target; no protocol fee on internal moves
+fn transfer_treasury(storage: &mut dyn Storage, env: &Env, source_tribe_id: u64, target_tribe_id: u64) -> StdResult<()> {
+    let balances = TREASURY
+        .prefix(source_tribe_id)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    
+    for (denom, amount) in balances {
+        TREASURY.remove(storage, (source_tribe_id, denom.as_str()));
+        if amount.is_zero() {
+            continue;
+        }
+        TREASURY.update(storage, (target_tribe_id, denom.as_str()), |balance| -> StdResult<_> {
+            Ok(balance.unwrap_or_default().checked_add(amount)?)
+        })?;
+        
+        let coin = Coin { denom, amount };
+        record_treasury_entry(storage, env, source_tribe_id, &coin, TreasuryEntryKind::MergeOut, Uint128::zero(), &env.contract.address)?;
+        record_treasury_entry(storage, env, target_tribe_id, &coin, TreasuryEntryKind::MergeIn, Uint128::zero(), &env.contract.address)?;
+    }
+    Ok(())
 }
 
 pub fn revokeInviteCode(
@@ -1194,23 +1912,178 @@ pub fn revokeInviteCode(
     // Check if sender is tribe admin
     only_tribe_admin!(deps, info, tribe_id);
     
-    // Get tribe metadata
-    let mut tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
+    let code_hash = hash_invite_code(&code);
+    if !INVITE_CODE.has(deps.storage, (tribe_id, &code_hash)) {
+        return Err(cosmwasm_std::StdError::generic_err("Invite code not found"));
+    }
+    INVITE_CODE.remove(deps.storage, (tribe_id, &code_hash));
     
-    // Validate code
-    let code_hash = Sha256::digest(code.as_bytes()).to_vec();
+    Ok(Response::new()
+        .add_attribute("action", "revoke_invite_code")
+        .add_attribute("tribe_id", tribe_id.to_string()))
+}
+
+pub fn depositTreasury(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tribe_id: u64,
+) -> StdResult<Response> {
+    let tribe_meta = TRIBE_META.may_load(deps.storage, &tribe_id.to_string())?
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("Tribe not found"))?;
+    // Merged or deactivated tribes can no longer spend what they receive
+    if !tribe_meta.is_active {
+        return Err(cosmwasm_std::StdError::generic_err("Tribe not active"));
+    }
+    if info.funds.is_empty() {
+        return Err(cosmwasm_std::StdError::generic_err("No funds sent"));
+    }
     
-    // Remove from whitelist if applicable
-    if let Some(addr) = deps.api.addr_validate(&code).ok() {
-        tribe_meta.whitelist.retain(|a| a != &addr);
+    let mut fee_msgs = vec![];
+    for coin in &info.funds {
+        fee_msgs.extend(credit_treasury(deps.storage, &env, tribe_id, coin, TreasuryEntryKind::Deposit, &info.sender)?);
     }
     
-    // Save updated tribe metadata
-    TRIBE_META.save(deps.storage, &tribe_id.to_string(), &tribe_meta)?;
+    Ok(Response::new()
+        .add_messages(fee_msgs)
+        .add_attribute("action", "deposit_treasury")
+        .add_attribute("tribe_id", tribe_id.to_string())
+        .add_attribute("depositor", info.sender.to_string()))
+}
+
+// Only the tribe owner can move funds out of the treasury
+pub fn withdrawTreasury(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    tribe_id: u64,
+    amount: Coin,
+    recipient: String,
+) -> StdResult<Response> {
+    let tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
+    if info.sender != tribe_meta.admin {
+        return Err(cosmwasm_std::StdError::generic_err("Not tribe owner"));
+    }
+    if amount.amount.is_zero() {
+        return Err(cosmwasm_std::StdError::generic_err("Withdrawal amount must be non-zero"));
+    }
+    let recipient_addr = deps.api.addr_validate(&recipient)?;
+    
+    let balance = TREASURY.may_load(deps.storage, (tribe_id, amount.denom.as_str()))?.unwrap_or_default();
+    if balance < amount.amount {
+        return Err(cosmwasm_std::StdError::generic_err("Insufficient treasury balance"));
+    }
+    TREASURY.save(deps.storage, (tribe_id, amount.denom.as_str()), &(balance - amount.amount))?;
+    record_treasury_entry(deps.storage, &env, tribe_id, &amount, TreasuryEntryKind::Withdrawal, Uint128::zero(), &recipient_addr)?;
     
     Ok(Response::new()
-        .add_attribute("action", "revoke_invite_code")
-        .add_attribute("tribe_id", tribe_id.to_string()))
+        .add_message(BankMsg::Send {
+            to_address: recipient_addr.to_string(),
+            amount: vec![amount.clone()],
+        })
+        .add_attribute("action", "withdraw_treasury")
+        .add_attribute("tribe_id", tribe_id.to_string())
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("recipient", recipient_addr.to_string()))
+}
+
+// tribe_controller has no owner of its own, so only role_manager's platform-wide pause applies
+pub fn setPaused(
+    deps: DepsMut,
+    info: MessageInfo,
+    paused: bool,
+) -> StdResult<Response> {
+    let config = get_config(deps.storage)?;
+    if info.sender != config.role_manager {
+        return Err(cosmwasm_std::StdError::generic_err("Unauthorized"));
+    }
+    
+    PAUSED.save(deps.storage, &paused)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", if paused { "pause" } else { "unpause" })
+        .add_attribute("paused_by", info.sender.to_string()))
+}
+
+pub fn updateProtocolFee(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_bps: u16,
+    fee_collector: Option<String>,
+) -> StdResult<Response> {
+    if !can_perform(deps.as_ref(), &info.sender, ACTION_SET_PROTOCOL_FEE)? {
+        return Err(cosmwasm_std::StdError::generic_err("Unauthorized"));
+    }
+    if fee_bps > MAX_PROTOCOL_FEE_BPS {
+        return Err(cosmwasm_std::StdError::generic_err(format!("Protocol fee cannot exceed {} bps", MAX_PROTOCOL_FEE_BPS)));
+    }
+    
+    let mut config = get_config(deps.storage)?;
+    config.protocol_fee_bps = fee_bps;
+    config.fee_collector = fee_collector.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    CONFIG.save(deps.storage, &config)?;
+    
+    Ok(Response::new()
+        .add_attribute("action", "update_protocol_fee")
+        .add_attribute("fee_bps", fee_bps.to_string())
+        .add_attribute("fee_collector", config.fee_collector.map(|addr| addr.to_string()).unwrap_or_default()))
+}
+
+pub fn executeOnBehalf(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    let action = session_action(&msg)
+        .ok_or_else(|| cosmwasm_std::StdError::generic_err("Message cannot be sent by a session key"))?;
+    
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    if !is_session_authorized(deps.as_ref(), &owner_addr, &info.sender, action)? {
+        return Err(cosmwasm_std::StdError::generic_err("Session not authorized"));
+    }
+    
+    // Run the inner message as the owner; entry fees still come from the session key
+    let session = info.sender;
+    let owner_info = MessageInfo {
+        sender: owner_addr,
+        funds: info.funds,
+    };
+    let response = execute(deps, env, owner_info, msg)?;
+    
+    Ok(response
+        .add_attribute("on_behalf_of", owner)
+        .add_attribute("session", session))
+}
+
+pub fn roleChanged(
+    deps: DepsMut,
+    info: MessageInfo,
+    user: String,
+    role: String,
+    scope: Option<String>,
+    granted: bool,
+) -> StdResult<Response> {
+    // Only role_manager may push role updates
+    let config = get_config(deps.storage)?;
+    if info.sender != config.role_manager {
+        return Err(cosmwasm_std::StdError::generic_err("Unauthorized"));
+    }
+    
+    // Tribe admin rights are checked live through CanPerform, so a revoked moderator
+    // loses them immediately; the notification only needs to be surfaced for indexers
+    let tribe_id = scope
+        .as_deref()
+        .and_then(|scope| scope.strip_prefix("tribe:"))
+        .and_then(|tribe_id| tribe_id.parse::<u64>().ok());
+    
+    Ok(Response::new()
+        .add_attribute("action", "role_changed")
+        .add_attribute("user", user)
+        .add_attribute("role", role)
+        .add_attribute("tribe_id", tribe_id.map(|tribe_id| tribe_id.to_string()).unwrap_or_default())
+        .add_attribute("granted", granted.to_string()))
 }
 
 pub fn cancelMerge(
@@ -1225,6 +2098,10 @@ pub fn cancelMerge(
     // Check if sender is source tribe admin
     only_tribe_admin!(deps, info, merge_request.source_tribe_id);
     
+    if merge_request.in_progress {
+        return Err(cosmwasm_std::StdError::generic_err("Merge already in progress"));
+    }
+    
     // Remove merge request
     MERGE_REQUEST.remove(deps.storage, &merge_request_id.to_string());
     
@@ -1237,12 +2114,11 @@ pub fn cancelMerge(
 
 pub fn getMemberStatus(deps: Deps, tribe_id: u64, member: String) -> StdResult<MemberStatusResponse> {
     let member_addr = deps.api.addr_validate(&member)?;
-    let member_status_key = format!("{}:{}", tribe_id, member_addr);
-    let status = MEMBER_STATUS.may_load(deps.storage, &member_status_key)?.unwrap_or(MemberStatus::NONE);
+    let status = member_status(deps.storage, tribe_id, &member_addr)?;
     Ok(MemberStatusResponse { status })
 }
 
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetTribeAdmin { tribe_id } => to_json_binary(&getTribeAdmin(deps, tribe_id)?),
         QueryMsg::GetTribeWhitelist { tribe_id } => to_json_binary(&getTribeWhitelist(deps, tribe_id)?),
@@ -1251,9 +2127,15 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetTribeConfigView { tribe_id } => to_json_binary(&getTribeConfigView(deps, tribe_id)?),
         QueryMsg::GetMemberCount { tribe_id } => to_json_binary(&getMemberCount(deps, tribe_id)?),
         QueryMsg::GetUserTribes { user } => to_json_binary(&getUserTribes(deps, user)?),
-        QueryMsg::GetInviteCodeStatus { tribe_id, code } => to_json_binary(&getInviteCodeStatus(deps, tribe_id, code)?),
+        QueryMsg::GetInviteCodeStatus { tribe_id, code } => to_json_binary(&getInviteCodeStatus(deps, env, tribe_id, code)?),
         QueryMsg::GetMergeRequest { request_id } => to_json_binary(&getMergeRequest(deps, request_id)?),
-        QueryMsg::GetTribeDetails { tribe_id } => to_json_binary(&getTribeDetails(deps, tribe_id)?),
+        QueryMsg::GetTribeDetails { tribe_id } => to_json_binary(&getTribeDetails(deps, env, tribe_id)?),
+        QueryMsg::GetTreasury { tribe_id } => to_json_binary(&getTreasury(deps, tribe_id)?),
+        QueryMsg::GetTreasuryHistory { tribe_id, start_after, limit } => to_json_binary(&getTreasuryHistory(deps, tribe_id, start_after, limit)?),
+        QueryMsg::GetProtocolFee {} => to_json_binary(&getProtocolFee(deps)?),
+        QueryMsg::IsPaused {} => to_json_binary(&BoolResponse { result: PAUSED.may_load(deps.storage)?.unwrap_or(false) }),
+        QueryMsg::ListMembers { tribe_id, status, start_after, limit } => to_json_binary(&listMembers(deps, tribe_id, status, start_after, limit)?),
+        QueryMsg::ListPendingRequests { tribe_id, start_after, limit } => to_json_binary(&listPendingRequests(deps, tribe_id, start_after, limit)?),
     }
 }
 
@@ -1277,6 +2159,72 @@ pub fn isAddressWhitelisted(deps: Deps, tribe_id: u64, user: String) -> StdResul
     Ok(BoolResponse { result: is_whitelisted })
 }
 
+pub fn getTreasury(deps: Deps, tribe_id: u64) -> StdResult<TreasuryResponse> {
+    let balances = TREASURY
+        .prefix(tribe_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TreasuryResponse { balances })
+}
+
+// Newest entries first
+pub fn getTreasuryHistory(deps: Deps, tribe_id: u64, start_after: Option<u64>, limit: Option<u32>) -> StdResult<TreasuryHistoryResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+    
+    let entries = TREASURY_HISTORY
+        .prefix(tribe_id)
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(_, entry)| entry))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(TreasuryHistoryResponse { entries })
+}
+
+pub fn listMembers(deps: Deps, tribe_id: u64, status: Option<MemberStatus>, start_after: Option<(u64, String)>, limit: Option<u32>) -> StdResult<MembersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|(joined_at, addr)| deps.api.addr_validate(&addr).map(|addr| (joined_at, addr)))
+        .transpose()?;
+    let statuses = match status {
+        Some(status) => vec![status],
+        None => vec![MemberStatus::ACTIVE, MemberStatus::Admin, MemberStatus::Member],
+    };
+    
+    // Each status is already in join order, so `limit` from each is enough to merge a page
+    let mut page = Vec::new();
+    for status in statuses {
+        let start = start_after
+            .clone()
+            .map(|(joined_at, addr)| Bound::exclusive((joined_at, (tribe_id, addr))));
+        let members = members()
+            .idx
+            .joined
+            .sub_prefix((tribe_id, member_status_key(&status)))
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, member)| member))
+            .collect::<StdResult<Vec<_>>>()?;
+        page.extend(members);
+    }
+    page.sort_by(|a, b| (a.joined_at, &a.member).cmp(&(b.joined_at, &b.member)));
+    page.truncate(limit);
+    Ok(MembersResponse { members: page })
+}
+
+pub fn listPendingRequests(deps: Deps, tribe_id: u64, start_after: Option<(u64, String)>, limit: Option<u32>) -> StdResult<MembersResponse> {
+    listMembers(deps, tribe_id, Some(MemberStatus::PENDING), start_after, limit)
+}
+
+pub fn getProtocolFee(deps: Deps) -> StdResult<ProtocolFeeResponse> {
+    let config = get_config(deps.storage)?;
+    Ok(ProtocolFeeResponse {
+        fee_bps: config.protocol_fee_bps,
+        fee_collector: config.fee_collector.map(|addr| addr.to_string()),
+    })
+}
+
 pub fn getTribeConfigView(deps: Deps, tribe_id: u64) -> StdResult<TribeConfigViewResponse> {
     let tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
     let config = TribeConfigView {
@@ -1295,33 +2243,32 @@ pub fn getMemberCount(deps: Deps, tribe_id: u64) -> StdResult<MemberCountRespons
 
 pub fn getUserTribes(deps: Deps, user: String) -> StdResult<UserTribesResponse> {
     let user_addr = deps.api.addr_validate(&user)?;
-    let config = CONFIG.load(deps.storage)?;
-    let mut tribe_ids = Vec::new();
-    
-    for tribe_id in 0..config.next_tribe_id {
-        let is_member_key = format!("{}:{}", tribe_id, user_addr);
-        if let Some(true) = IS_MEMBER.may_load(deps.storage, &is_member_key)? {
-            tribe_ids.push(tribe_id);
-        }
-    }
+    let tribe_ids = members()
+        .idx
+        .user
+        .prefix(user_addr)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((_, member)) => is_member_status(&member.status).then_some(Ok(member.tribe_id)),
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
     
     Ok(UserTribesResponse { tribe_ids })
 }
 
-pub fn getInviteCodeStatus(deps: Deps, tribe_id: u64, code: String) -> StdResult<InviteCodeStatusResponse> {
-    let tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
+pub fn getInviteCodeStatus(deps: Deps, env: Env, tribe_id: u64, code: String) -> StdResult<InviteCodeStatusResponse> {
+    let code_hash = hash_invite_code(&code);
+    let invite_code = INVITE_CODE.may_load(deps.storage, (tribe_id, &code_hash))?;
     
-    // Check if code is valid for this tribe
-    let is_valid = if let Ok(addr) = deps.api.addr_validate(&code) {
-        tribe_meta.whitelist.contains(&addr)
-    } else {
-        false
+    let response = match invite_code {
+        Some(invite_code) if invite_code.is_live(env.block.time.seconds()) => InviteCodeStatusResponse {
+            valid: true,
+            remaining_uses: invite_code.max_uses - invite_code.used_count,
+        },
+        _ => InviteCodeStatusResponse { valid: false, remaining_uses: 0 },
     };
-    
-    Ok(InviteCodeStatusResponse {
-        valid: is_valid,
-        remaining_uses: if is_valid { 1 } else { 0 }, // Simplified
-    })
+    Ok(response)
 }
 
 pub fn getMergeRequest(deps: Deps, request_id: u64) -> StdResult<MergeRequestResponse> {
@@ -1341,10 +2288,7 @@ pub fn is_tribe_member_with_status(
     addr: &Addr, 
     status: MemberStatus
 ) -> StdResult<bool> {
-    match TRIBE_MEMBERS.may_load(deps.storage, (u64_to_key(tribe_id), addr))? {
-        Some(member) => Ok(member.status == status),
-        None => Ok(false),
-    }
+    Ok(member_status(deps.storage, tribe_id, addr)? == status)
 }
 
 // Helper function to check if a user is a tribe admin
@@ -1354,7 +2298,71 @@ pub fn is_tribe_admin_check(deps: Deps, tribe_id: u64, addr: &Addr) -> StdResult
 
 // Helper function to check if a user is any type of tribe member
 pub fn is_tribe_member(deps: Deps, tribe_id: u64, addr: &Addr) -> StdResult<bool> {
-    Ok(TRIBE_MEMBERS.has(deps.storage, (u64_to_key(tribe_id), addr)))
+    Ok(is_member_status(&member_status(deps.storage, tribe_id, addr)?))
+}
+
+// Statuses that count as belonging to the tribe
+fn is_member_status(status: &MemberStatus) -> bool {
+    matches!(status, MemberStatus::ACTIVE | MemberStatus::Admin | MemberStatus::Member)
+}
+
+// Stored in the join-time index, so these must never change with the variant names
+fn member_status_key(status: &MemberStatus) -> String {
+    match status {
+        MemberStatus::NONE => "NONE",
+        MemberStatus::PENDING => "PENDING",
+        MemberStatus::ACTIVE => "ACTIVE",
+        MemberStatus::BANNED => "BANNED",
+        MemberStatus::Admin => "Admin",
+        MemberStatus::Member => "Member",
+    }
+    .to_string()
+}
+
+pub fn member_status(storage: &dyn Storage, tribe_id: u64, addr: &Addr) -> StdResult<MemberStatus> {
+    Ok(members()
+        .may_load(storage, (tribe_id, addr))?
+        .map_or(MemberStatus::NONE, |member| member.status))
+}
+
+// Single write path for membership; keeps MEMBER_COUNT in step with member statuses
+fn set_member_status(storage: &mut dyn Storage, now: u64, tribe_id: u64, addr: &Addr, status: MemberStatus) -> StdResult<()> {
+    let existing = members().may_load(storage, (tribe_id, addr))?;
+    let was_member = existing.as_ref().is_some_and(|member| is_member_status(&member.status));
+    let is_member = is_member_status(&status);
+    
+    // Requesting and joining restart the clock; other changes (e.g. bans) keep it
+    let restarts = status == MemberStatus::PENDING || (is_member && !was_member);
+    let joined_at = match &existing {
+        Some(member) if !restarts => member.joined_at,
+        _ => now,
+    };
+    
+    let member = TribeMember {
+        tribe_id,
+        member: addr.clone(),
+        status,
+        joined_at,
+    };
+    members().save(storage, (tribe_id, addr), &member)?;
+    update_member_count(storage, tribe_id, was_member, is_member)
+}
+
+fn remove_member(storage: &mut dyn Storage, tribe_id: u64, addr: &Addr) -> StdResult<()> {
+    let Some(existing) = members().may_load(storage, (tribe_id, addr))? else {
+        return Ok(());
+    };
+    members().remove(storage, (tribe_id, addr))?;
+    update_member_count(storage, tribe_id, is_member_status(&existing.status), false)
+}
+
+fn update_member_count(storage: &mut dyn Storage, tribe_id: u64, was_member: bool, is_member: bool) -> StdResult<()> {
+    if was_member == is_member {
+        return Ok(());
+    }
+    let count = MEMBER_COUNT.may_load(storage, &tribe_id.to_string())?.unwrap_or(0);
+    let count = if is_member { count + 1 } else { count.saturating_sub(1) };
+    MEMBER_COUNT.save(storage, &tribe_id.to_string(), &count)
 }
 
 fn is_tribe_admin_internal(
@@ -1369,11 +2377,12 @@ fn is_tribe_admin_internal(
         return Ok(true);
     }
     
-    // Check if addr has MODERATOR_ROLE
+    // Check if addr may moderate this tribe per role_manager's permission matrix
     let config = get_config(storage)?;
-    let msg = to_json_binary(&RoleManagerQuery::HasRole {
+    let msg = to_json_binary(&RoleManagerQuery::CanPerform {
         user: addr.to_string(),
-        role: "MODERATOR_ROLE".to_string(),
+        action: ACTION_MODERATE.to_string(),
+        scope: Some(tribe_scope(tribe_id)),
     })?;
     
     let query = cosmwasm_std::WasmQuery::Smart {
@@ -1381,8 +2390,8 @@ fn is_tribe_admin_internal(
         msg,
     };
     
-    match deps.querier.query::<RoleResponse>(&query.into()) {
-        Ok(response) => Ok(response.has_role),
+    match deps.querier.query::<BoolResponse>(&query.into()) {
+        Ok(response) => Ok(response.result),
         Err(_) => Ok(false),
     }
 }
@@ -1395,24 +2404,33 @@ pub struct TribeDetailsView {
     pub admin: Addr,
     pub whitelist: Vec<Addr>,
     pub join_type: JoinType,
-    pub entry_fee: Uint128,
+    pub entry_fee: Option<Coin>,
     pub nft_requirements: Vec<NFTRequirement>,
     pub member_count: u64,
     pub can_merge: bool,
     pub is_active: bool,
+    pub merged_into: Option<u64>,
     pub available_invite_codes: Vec<Vec<u8>>,
 }
 
 // Add getTribeDetails function to match Solidity function
-pub fn getTribeDetails(deps: Deps, tribe_id: u64) -> StdResult<TribeDetailsView> {
+pub fn getTribeDetails(deps: Deps, env: Env, tribe_id: u64) -> StdResult<TribeDetailsView> {
     // Load tribe metadata
     let tribe_meta = TRIBE_META.load(deps.storage, &tribe_id.to_string())?;
     
     // Get member count
     let member_count = MEMBER_COUNT.may_load(deps.storage, &tribe_id.to_string())?.unwrap_or(0);
     
-    // Mock empty invite codes - in a real implementation, you'd fetch actual invite codes
-    let available_invite_codes = Vec::new();
+    // Hashes of the codes that can still be redeemed
+    let now = env.block.time.seconds();
+    let available_invite_codes = INVITE_CODE
+        .prefix(tribe_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((code_hash, invite_code)) => invite_code.is_live(now).then_some(Ok(code_hash)),
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
     
     Ok(TribeDetailsView {
         name: tribe_meta.name,
@@ -1425,6 +2443,445 @@ pub fn getTribeDetails(deps: Deps, tribe_id: u64) -> StdResult<TribeDetailsView>
         member_count,
         can_merge: tribe_meta.can_merge,
         is_active: tribe_meta.is_active,
+        merged_into: tribe_meta.merged_into,
         available_invite_codes,
     })
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_json, ContractResult, OwnedDeps, SystemResult, WasmQuery};
+
+    type TestDeps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    // role_manager stand-in: `blocked` are on the blocklist, `allowed` pass every CanPerform check,
+    // and "phone" is a session key of every owner for joins
+    fn mock_role_manager(deps: &mut TestDeps, blocked: &[&str], allowed: &[&str]) {
+        let blocked = blocked.iter().map(|addr| addr.to_string()).collect::<Vec<_>>();
+        let allowed = allowed.iter().map(|addr| addr.to_string()).collect::<Vec<_>>();
+        deps.querier.update_wasm(move |query| {
+            let result = match query {
+                WasmQuery::Smart { msg, .. } => match from_json(msg).unwrap() {
+                    RoleManagerQuery::IsBlocked { user } => blocked.contains(&user),
+                    RoleManagerQuery::CanPerform { user, .. } => allowed.contains(&user),
+                    RoleManagerQuery::IsSessionAuthorized { session, action, .. } => session == "phone" && action == SESSION_ACTION_JOIN,
+                    _ => false,
+                },
+                _ => false,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&BoolResponse { result }).unwrap()))
+        });
+    }
+
+    fn setup() -> TestDeps {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg { role_manager: "role_manager".to_string() }).unwrap();
+        mock_role_manager(&mut deps, &[], &[]);
+        WHITELIST.save(deps.as_mut().storage, &Addr::unchecked("owner"), &true).unwrap();
+        deps
+    }
+
+    // Creates a tribe owned by "owner" and returns its id
+    fn create_tribe(deps: &mut TestDeps, join_type: JoinType, entry_fee: Option<Coin>) -> u64 {
+        let res = createTribe(deps.as_mut(), mock_env(), mock_info("owner", &[]), "tribe".to_string(), "".to_string(), vec![], join_type, entry_fee, vec![]).unwrap();
+        res.attributes[1].value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_execute_on_behalf() {
+        let mut deps = setup();
+        let tribe_id = create_tribe(&mut deps, JoinType::PUBLIC, None);
+
+        let join = ExecuteMsg::ExecuteOnBehalf { owner: "alice".to_string(), msg: Box::new(ExecuteMsg::JoinTribe { tribe_id }) };
+        let res = execute(deps.as_mut(), mock_env(), mock_info("phone", &[]), join.clone()).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("member", "alice")));
+        assert_eq!(MemberStatus::ACTIVE, member_status(deps.as_ref().storage, tribe_id, &Addr::unchecked("alice")).unwrap());
+
+        let err = execute(deps.as_mut(), mock_env(), mock_info("laptop", &[]), join).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Session not authorized"), err);
+        let ban = ExecuteMsg::ExecuteOnBehalf {
+            owner: "owner".to_string(),
+            msg: Box::new(ExecuteMsg::BanMember { tribe_id, member: "alice".to_string() }),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("phone", &[]), ban).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Message cannot be sent by a session key"), err);
+    }
+
+    #[test]
+    fn test_platform_pause() {
+        let mut deps = setup();
+        let tribe_id = create_tribe(&mut deps, JoinType::PUBLIC, None);
+
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), ExecuteMsg::Pause {}).unwrap_err();
+        execute(deps.as_mut(), mock_env(), mock_info("role_manager", &[]), ExecuteMsg::Pause {}).unwrap();
+        let paused: BoolResponse = from_json(query(deps.as_ref(), mock_env(), QueryMsg::IsPaused {}).unwrap()).unwrap();
+        assert!(paused.result);
+
+        // Joins, treasury moves and merges all stop
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::JoinTribe { tribe_id }).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Contract is paused"), err);
+        let withdraw = ExecuteMsg::WithdrawTreasury { tribe_id, amount: Coin::new(1, "uxion"), recipient: "owner".to_string() };
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), withdraw).unwrap_err();
+
+        execute(deps.as_mut(), mock_env(), mock_info("role_manager", &[]), ExecuteMsg::Unpause {}).unwrap();
+        execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), ExecuteMsg::JoinTribe { tribe_id }).unwrap();
+    }
+
+    #[test]
+    fn test_entry_fees() {
+        let mut deps = setup();
+        let fee = Coin::new(100, "uxion");
+        let funds = vec![fee.clone()];
+        let public_id = create_tribe(&mut deps, JoinType::PUBLIC, Some(fee.clone()));
+        let private_id = create_tribe(&mut deps, JoinType::PRIVATE, Some(fee.clone()));
+
+        // The fee must be paid exactly, in one coin
+        let err = joinTribe(deps.as_mut(), mock_env(), mock_info("alice", &[Coin::new(99, "uxion")]), public_id).unwrap_err();
+        assert_eq!(ContractError::Std(cosmwasm_std::StdError::generic_err("Entry fee must be paid as exactly 100uxion")), err);
+        joinTribe(deps.as_mut(), mock_env(), mock_info("alice", &[fee.clone(), Coin::new(1, "uatom")]), public_id).unwrap_err();
+        joinTribe(deps.as_mut(), mock_env(), mock_info("alice", &funds), public_id).unwrap();
+        assert_eq!(vec![fee.clone()], getTreasury(deps.as_ref(), public_id).unwrap().balances);
+
+        // Join requests hold the fee until approved, and refund it when rejected
+        requestToJoinTribe(deps.as_mut(), mock_env(), mock_info("bob", &funds), private_id).unwrap();
+        requestToJoinTribe(deps.as_mut(), mock_env(), mock_info("carol", &funds), private_id).unwrap();
+        assert!(getTreasury(deps.as_ref(), private_id).unwrap().balances.is_empty());
+
+        approveMember(deps.as_mut(), mock_env(), mock_info("owner", &[]), private_id, "bob".to_string()).unwrap();
+        assert_eq!(vec![fee.clone()], getTreasury(deps.as_ref(), private_id).unwrap().balances);
+
+        let res = rejectMember(deps.as_mut(), mock_env(), mock_info("owner", &[]), private_id, "carol".to_string()).unwrap();
+        let refund: CosmosMsg = BankMsg::Send { to_address: "carol".to_string(), amount: vec![fee.clone()] }.into();
+        assert_eq!(vec![refund], res.messages.into_iter().map(|sub| sub.msg).collect::<Vec<_>>());
+        assert!(!ESCROWED_FEES.has(deps.as_ref().storage, (private_id, &Addr::unchecked("carol"))));
+        assert_eq!(vec![fee], getTreasury(deps.as_ref(), private_id).unwrap().balances);
+    }
+
+    #[test]
+    fn test_treasury() {
+        let mut deps = setup();
+        let tribe_id = create_tribe(&mut deps, JoinType::PUBLIC, None);
+        mock_role_manager(&mut deps, &[], &["platform_admin"]);
+
+        // Only the permission matrix may set the protocol fee, and it is capped
+        updateProtocolFee(deps.as_mut(), mock_info("owner", &[]), 500, Some("collector".to_string())).unwrap_err();
+        let err = updateProtocolFee(deps.as_mut(), mock_info("platform_admin", &[]), 1_001, Some("collector".to_string())).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Protocol fee cannot exceed 1000 bps"), err);
+        updateProtocolFee(deps.as_mut(), mock_info("platform_admin", &[]), 500, Some("collector".to_string())).unwrap();
+
+        // Deposits are split between the collector and the treasury
+        let res = depositTreasury(deps.as_mut(), mock_env(), mock_info("alice", &[Coin::new(1_000, "uxion")]), tribe_id).unwrap();
+        let fee: CosmosMsg = BankMsg::Send { to_address: "collector".to_string(), amount: vec![Coin::new(50, "uxion")] }.into();
+        assert_eq!(vec![fee], res.messages.into_iter().map(|sub| sub.msg).collect::<Vec<_>>());
+        assert_eq!(vec![Coin::new(950, "uxion")], getTreasury(deps.as_ref(), tribe_id).unwrap().balances);
+        let history = getTreasuryHistory(deps.as_ref(), tribe_id, None, None).unwrap().entries;
+        assert_eq!(Uint128::new(50), history[0].protocol_fee);
+
+        // Only the owner withdraws, and never more than the balance
+        let withdraw = |amount: u128| ExecuteMsg::WithdrawTreasury { tribe_id, amount: Coin::new(amount, "uxion"), recipient: "alice".to_string() };
+        let err = execute(deps.as_mut(), mock_env(), mock_info("alice", &[]), withdraw(950)).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Not tribe owner"), err);
+        let err = execute(deps.as_mut(), mock_env(), mock_info("platform_admin", &[]), withdraw(950)).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Not tribe owner"), err);
+        let err = execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), withdraw(951)).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Insufficient treasury balance"), err);
+        execute(deps.as_mut(), mock_env(), mock_info("owner", &[]), withdraw(950)).unwrap();
+        assert_eq!(Uint128::zero(), TREASURY.load(deps.as_ref().storage, (tribe_id, "uxion")).unwrap());
+
+        // Inactive tribes, e.g. merged ones, take no deposits
+        let mut meta = TRIBE_META.load(deps.as_ref().storage, &tribe_id.to_string()).unwrap();
+        meta.is_active = false;
+        TRIBE_META.save(deps.as_mut().storage, &tribe_id.to_string(), &meta).unwrap();
+        let err = depositTreasury(deps.as_mut(), mock_env(), mock_info("alice", &[Coin::new(1_000, "uxion")]), tribe_id).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Tribe not active"), err);
+    }
+
+    #[test]
+    fn test_invite_codes() {
+        let mut deps = setup();
+        let tribe_id = create_tribe(&mut deps, JoinType::INVITE_CODE, None);
+        let now = mock_env().block.time.seconds();
+        let owner_info = mock_info("owner", &[]);
+
+        let err = createInviteCode(deps.as_mut(), mock_env(), owner_info.clone(), tribe_id, "vip".to_string(), 0, now + 100).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Invite code needs at least one use"), err);
+        createInviteCode(deps.as_mut(), mock_env(), owner_info.clone(), tribe_id, "vip".to_string(), 2, now).unwrap_err();
+        createInviteCode(deps.as_mut(), mock_env(), mock_info("alice", &[]), tribe_id, "vip".to_string(), 2, now + 100).unwrap_err();
+        createInviteCode(deps.as_mut(), mock_env(), owner_info.clone(), tribe_id, "vip".to_string(), 2, now + 100).unwrap();
+        createInviteCode(deps.as_mut(), mock_env(), owner_info.clone(), tribe_id, "vip".to_string(), 5, now + 100).unwrap_err();
+
+        // Each redemption uses up the code; members type the same text the owner created
+        let join = |deps: &mut TestDeps, user: &str, code: &str, env: Env| {
+            let msg = ExecuteMsg::JoinTribeWithCode { tribe_id, invite_code: code.to_string() };
+            execute(deps.as_mut(), env, mock_info(user, &[]), msg)
+        };
+        let err = join(&mut deps, "alice", "nope", mock_env()).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Invalid invite code"), err);
+        join(&mut deps, "alice", "vip", mock_env()).unwrap();
+        assert_eq!(1, getInviteCodeStatus(deps.as_ref(), mock_env(), tribe_id, "vip".to_string()).unwrap().remaining_uses);
+        join(&mut deps, "bob", "vip", mock_env()).unwrap();
+        let err = join(&mut deps, "carol", "vip", mock_env()).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Invite code fully used"), err);
+        assert!(!getInviteCodeStatus(deps.as_ref(), mock_env(), tribe_id, "vip".to_string()).unwrap().valid);
+
+        // Codes stop working at their expiry, and revoked codes are gone
+        createInviteCode(deps.as_mut(), mock_env(), owner_info.clone(), tribe_id, "early".to_string(), 5, now + 100).unwrap();
+        let mut later = mock_env();
+        later.block.time = later.block.time.plus_seconds(100);
+        let err = join(&mut deps, "carol", "early", later).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Invite code expired"), err);
+        revokeInviteCode(deps.as_mut(), mock_env(), owner_info, tribe_id, "early".to_string()).unwrap();
+        let err = join(&mut deps, "carol", "early", mock_env()).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Invalid invite code"), err);
+    }
+
+    #[test]
+    fn test_migrate_invite_codes() {
+        let mut deps = setup();
+        let tribe_id = create_tribe(&mut deps, JoinType::INVITE_CODE, None);
+        let code_hash = hash_invite_code("legacy");
+        let legacy = InviteCode { code_hash: code_hash.clone(), max_uses: 3, used_count: 1, expiry_time: mock_env().block.time.seconds() + 100 };
+        let key = format!("{}:{}", tribe_id, hex::encode(&code_hash));
+        LEGACY_INVITE_CODE.save(deps.as_mut().storage, &key, &legacy).unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: None, limit: None }).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("migrated_invite_codes", "1")));
+        assert!(LEGACY_INVITE_CODE.is_empty(deps.as_ref().storage));
+
+        // Existing codes keep their remaining uses
+        assert_eq!(2, getInviteCodeStatus(deps.as_ref(), mock_env(), tribe_id, "legacy".to_string()).unwrap().remaining_uses);
+        joinTribeWithCode(deps.as_mut(), mock_env(), mock_info("alice", &[]), tribe_id, "legacy".to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_execute_merge() {
+        let mut deps = setup();
+        let source = create_tribe(&mut deps, JoinType::PRIVATE, None);
+        let target = create_tribe(&mut deps, JoinType::PRIVATE, None);
+        let owner_info = mock_info("owner", &[]);
+        for (tribe_id, addr) in [(source, "alice"), (source, "bob"), (source, "carol"), (source, "dave"), (target, "bob"), (target, "carol")] {
+            requestToJoinTribe(deps.as_mut(), mock_env(), mock_info(addr, &[]), tribe_id).unwrap();
+        }
+        for (tribe_id, addr) in [(source, "alice"), (source, "bob"), (source, "carol"), (target, "bob")] {
+            approveMember(deps.as_mut(), mock_env(), owner_info.clone(), tribe_id, addr.to_string()).unwrap();
+        }
+        banMember(deps.as_mut(), mock_env(), owner_info.clone(), target, "carol".to_string()).unwrap();
+
+        let res = requestMerge(deps.as_mut(), mock_env(), owner_info.clone(), source, target).unwrap();
+        let merge_request_id: u64 = res.attributes[1].value.parse().unwrap();
+        let err = executeMerge(deps.as_mut(), mock_env(), owner_info.clone(), merge_request_id, None).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Merge request not approved"), err);
+        approveMerge(deps.as_mut(), mock_env(), owner_info.clone(), merge_request_id).unwrap();
+
+        // The first chunk closes the source tribe and leaves the rest for later calls
+        let res = executeMerge(deps.as_mut(), mock_env(), owner_info.clone(), merge_request_id, Some(2)).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("moved_members", "1")));
+        assert!(res.attributes.contains(&cosmwasm_std::attr("completed", "false")));
+        assert!(!TRIBE_META.load(deps.as_ref().storage, &source.to_string()).unwrap().is_active);
+        let err = requestToJoinTribe(deps.as_mut(), mock_env(), mock_info("erin", &[]), source).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Tribe not active"), err);
+        let err = approveMember(deps.as_mut(), mock_env(), owner_info.clone(), source, "dave".to_string()).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Tribe not active"), err);
+
+        // Later chunks stop while the target is inactive rather than reviving it
+        let mut target_meta = TRIBE_META.load(deps.as_ref().storage, &target.to_string()).unwrap();
+        target_meta.is_active = false;
+        TRIBE_META.save(deps.as_mut().storage, &target.to_string(), &target_meta).unwrap();
+        let err = executeMerge(deps.as_mut(), mock_env(), owner_info.clone(), merge_request_id, None).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Target tribe not active"), err);
+        assert!(!TRIBE_META.load(deps.as_ref().storage, &target.to_string()).unwrap().is_active);
+        target_meta.is_active = true;
+        TRIBE_META.save(deps.as_mut().storage, &target.to_string(), &target_meta).unwrap();
+
+        // Existing target members (owner, bob) and target bans (carol) are not moved; pending dave is dropped
+        let res = executeMerge(deps.as_mut(), mock_env(), owner_info, merge_request_id, None).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("total_moved_members", "1")));
+        assert!(res.attributes.contains(&cosmwasm_std::attr("completed", "true")));
+        assert_eq!(3, getMemberCount(deps.as_ref(), target).unwrap().count);
+        assert_eq!(0, getMemberCount(deps.as_ref(), source).unwrap().count);
+        assert_eq!(MemberStatus::ACTIVE, getMemberStatus(deps.as_ref(), target, "alice".to_string()).unwrap().status);
+        assert_eq!(MemberStatus::BANNED, getMemberStatus(deps.as_ref(), target, "carol".to_string()).unwrap().status);
+        assert_eq!(MemberStatus::NONE, getMemberStatus(deps.as_ref(), target, "dave".to_string()).unwrap().status);
+        assert!(members().prefix(source).range(deps.as_ref().storage, None, None, Order::Ascending).next().is_none());
+        assert_eq!(Some(target), TRIBE_META.load(deps.as_ref().storage, &source.to_string()).unwrap().merged_into);
+        assert!(!MERGE_REQUEST.has(deps.as_ref().storage, &merge_request_id.to_string()));
+    }
+
+    #[test]
+    fn test_list_members_pagination() {
+        let mut deps = setup();
+        let tribe_id = create_tribe(&mut deps, JoinType::PRIVATE, None);
+        let at = |offset: u64| {
+            let mut env = mock_env();
+            env.block.time = env.block.time.plus_seconds(offset);
+            env
+        };
+        let names = |page: MembersResponse| page.members.into_iter().map(|member| member.member.to_string()).collect::<Vec<_>>();
+        for (offset, addr) in ["carol", "alice", "dave", "bob"].into_iter().enumerate() {
+            requestToJoinTribe(deps.as_mut(), at(offset as u64 + 1), mock_info(addr, &[]), tribe_id).unwrap();
+        }
+
+        let page = listPendingRequests(deps.as_ref(), tribe_id, None, Some(2)).unwrap();
+        assert_eq!(vec!["carol", "alice"], names(page.clone()));
+        let last = page.members.last().unwrap();
+        let cursor = (last.joined_at, last.member.to_string());
+
+        // The cursor stays valid after its member is approved, and removed requests just drop out
+        approveMember(deps.as_mut(), at(10), mock_info("owner", &[]), tribe_id, "alice".to_string()).unwrap();
+        rejectMember(deps.as_mut(), at(10), mock_info("owner", &[]), tribe_id, "dave".to_string()).unwrap();
+        let page = listPendingRequests(deps.as_ref(), tribe_id, Some(cursor), Some(2)).unwrap();
+        assert_eq!(vec!["bob"], names(page));
+
+        // Without a status, every member kind is merged into one join-time order
+        approveMember(deps.as_mut(), at(10), mock_info("owner", &[]), tribe_id, "carol".to_string()).unwrap();
+        let page = listMembers(deps.as_ref(), tribe_id, None, None, Some(2)).unwrap();
+        assert_eq!(vec!["owner", "alice"], names(page.clone()));
+        let last = page.members.last().unwrap();
+        let page = listMembers(deps.as_ref(), tribe_id, None, Some((last.joined_at, last.member.to_string())), Some(2)).unwrap();
+        assert_eq!(vec!["carol"], names(page));
+    }
+
+    #[test]
+    fn test_migrate_members() {
+        let mut deps = setup();
+        let storage = deps.as_mut().storage;
+        let (alice, bob, carol, dave) = (Addr::unchecked("alice"), Addr::unchecked("bob"), Addr::unchecked("carol"), Addr::unchecked("dave"));
+        MEMBER_COUNT.save(storage, "7", &5).unwrap();
+        LEGACY_MEMBER_STATUS.save(storage, "7:alice", &MemberStatus::ACTIVE).unwrap();
+        LEGACY_MEMBER_STATUS.save(storage, "7:bob", &MemberStatus::PENDING).unwrap();
+        LEGACY_TRIBE_MEMBERS.save(storage, (u64_to_key(7), &bob), &TribeMember { tribe_id: 7, member: bob.clone(), status: MemberStatus::PENDING, joined_at: 42 }).unwrap();
+        LEGACY_MEMBER_STATUS.save(storage, "7:carol", &MemberStatus::BANNED).unwrap();
+        LEGACY_MEMBER_STATUS.save(storage, "8:dave", &MemberStatus::ACTIVE).unwrap();
+        LEGACY_USER_TRIBES.save(storage, &alice, &vec![7]).unwrap();
+
+        // A bounded call leaves the rest for the next one and keeps execute closed
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: None, limit: Some(2) }).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("migrated_members", "2")));
+        assert!(res.attributes.contains(&cosmwasm_std::attr("completed", "false")));
+        let err = execute(deps.as_mut(), mock_env(), mock_info("role_manager", &[]), ExecuteMsg::Pause {}).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Migration in progress"), err);
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: None, limit: None }).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("migrated_members", "2")));
+        assert!(res.attributes.contains(&cosmwasm_std::attr("completed", "true")));
+        assert!(LEGACY_MEMBER_STATUS.is_empty(deps.as_ref().storage));
+        assert!(LEGACY_TRIBE_MEMBERS.is_empty(deps.as_ref().storage));
+        assert!(LEGACY_USER_TRIBES.is_empty(deps.as_ref().storage));
+        assert!(MIGRATION_RECOUNTED.is_empty(deps.as_ref().storage));
+        execute(deps.as_mut(), mock_env(), mock_info("role_manager", &[]), ExecuteMsg::Pause {}).unwrap();
+
+        // Counts are rebuilt across both calls rather than trusting the stale value
+        assert_eq!(1, getMemberCount(deps.as_ref(), 7).unwrap().count);
+        assert_eq!(1, getMemberCount(deps.as_ref(), 8).unwrap().count);
+
+        // Pending requests keep their request time; other members join at migration time
+        let now = mock_env().block.time.seconds();
+        assert_eq!(now, members().load(deps.as_ref().storage, (7, &alice)).unwrap().joined_at);
+        assert_eq!(now, members().load(deps.as_ref().storage, (8, &dave)).unwrap().joined_at);
+        let pending = listPendingRequests(deps.as_ref(), 7, None, None).unwrap().members;
+        assert_eq!(vec![(bob, 42)], pending.into_iter().map(|member| (member.member, member.joined_at)).collect::<Vec<_>>());
+        assert_eq!(MemberStatus::BANNED, getMemberStatus(deps.as_ref(), 7, carol.to_string()).unwrap().status);
+    }
+
+    #[test]
+    fn test_migrate_members_without_status() {
+        let mut deps = setup();
+        let storage = deps.as_mut().storage;
+        let (erin, gina, hank) = (Addr::unchecked("erin"), Addr::unchecked("gina"), Addr::unchecked("hank"));
+        MEMBER_COUNT.save(storage, "7", &9).unwrap();
+        // Flag-only members, one of whom had left
+        LEGACY_IS_MEMBER.save(storage, "7:erin", &true).unwrap();
+        LEGACY_IS_MEMBER.save(storage, "7:frank", &false).unwrap();
+        // Members stored with their status but without a MEMBER_STATUS entry
+        LEGACY_TRIBE_MEMBERS.save(storage, (u64_to_key(7), &gina), &TribeMember { tribe_id: 7, member: gina.clone(), status: MemberStatus::PENDING, joined_at: 42 }).unwrap();
+        LEGACY_TRIBE_MEMBERS.save(storage, (u64_to_key(7), &hank), &TribeMember { tribe_id: 7, member: hank.clone(), status: MemberStatus::ACTIVE, joined_at: 10 }).unwrap();
+        LEGACY_IS_MEMBER.save(storage, "7:hank", &true).unwrap();
+
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: None, limit: Some(2) }).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("completed", "false")));
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: None, limit: None }).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("migrated_members", "1")));
+        assert!(res.attributes.contains(&cosmwasm_std::attr("completed", "true")));
+        assert!(LEGACY_IS_MEMBER.is_empty(deps.as_ref().storage));
+        assert!(LEGACY_TRIBE_MEMBERS.is_empty(deps.as_ref().storage));
+
+        // Flags become active members; stored members keep their status and join time
+        let now = mock_env().block.time.seconds();
+        assert_eq!(now, members().load(deps.as_ref().storage, (7, &erin)).unwrap().joined_at);
+        assert!(!members().has(deps.as_ref().storage, (7, &Addr::unchecked("frank"))));
+        assert_eq!(10, members().load(deps.as_ref().storage, (7, &hank)).unwrap().joined_at);
+        let pending = listPendingRequests(deps.as_ref(), 7, None, None).unwrap().members;
+        assert_eq!(vec![(gina, 42)], pending.into_iter().map(|member| (member.member, member.joined_at)).collect::<Vec<_>>());
+        assert_eq!(2, getMemberCount(deps.as_ref(), 7).unwrap().count);
+    }
+
+    #[test]
+    fn test_migrate_entry_fees() {
+        let mut deps = setup();
+        let legacy = |entry_fee: u128| LegacyTribeMeta {
+            name: "tribe".to_string(),
+            metadata: "".to_string(),
+            admin: Addr::unchecked("owner"),
+            whitelist: vec![],
+            join_type: JoinType::PUBLIC,
+            entry_fee: Uint128::new(entry_fee),
+            nft_requirements: vec![],
+            can_merge: true,
+            is_active: true,
+            member_count: None,
+        };
+        STORED_TRIBE_META.save(deps.as_mut().storage, "1", &StoredTribeMeta::Legacy(legacy(100))).unwrap();
+        TRIBE_META.load(deps.as_ref().storage, "1").unwrap_err();
+
+        // Paid tribes cannot be converted without a denom
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: None, limit: None }).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("entry_fee_denom is required to convert legacy entry fees"), err);
+
+        STORED_TRIBE_META.save(deps.as_mut().storage, "0", &StoredTribeMeta::Legacy(legacy(0))).unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: Some("uxion".to_string()), limit: None }).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("migrated_tribes", "2")));
+        assert_eq!(None, TRIBE_META.load(deps.as_ref().storage, "0").unwrap().entry_fee);
+        assert_eq!(Some(Coin::new(100, "uxion")), TRIBE_META.load(deps.as_ref().storage, "1").unwrap().entry_fee);
+
+        // Already converted tribes are left alone
+        let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { entry_fee_denom: None, limit: None }).unwrap();
+        assert!(res.attributes.contains(&cosmwasm_std::attr("migrated_tribes", "0")));
+    }
+
+    #[test]
+    fn test_approve_member_rechecks_blocklist() {
+        let mut deps = setup();
+        let tribe_id = create_tribe(&mut deps, JoinType::PRIVATE, None);
+        requestToJoinTribe(deps.as_mut(), mock_env(), mock_info("alice", &[]), tribe_id).unwrap();
+        requestToJoinTribe(deps.as_mut(), mock_env(), mock_info("bob", &[]), tribe_id).unwrap();
+
+        // Blocked after applying, so the pending request can no longer be approved
+        mock_role_manager(&mut deps, &["alice"], &[]);
+        let err = approveMember(deps.as_mut(), mock_env(), mock_info("owner", &[]), tribe_id, "alice".to_string()).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Address is blocked"), err);
+        approveMember(deps.as_mut(), mock_env(), mock_info("owner", &[]), tribe_id, "bob".to_string()).unwrap();
+
+        // An unreachable role_manager counts as blocked
+        deps.querier.update_wasm(|_| SystemResult::Ok(ContractResult::Err("unreachable".to_string())));
+        let err = requestToJoinTribe(deps.as_mut(), mock_env(), mock_info("carol", &[]), tribe_id).unwrap_err();
+        assert_eq!(cosmwasm_std::StdError::generic_err("Address is blocked"), err);
+    }
+
+    #[test]
+    fn test_role_changed_hook() {
+        let mut deps = mock_dependencies();
+        instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), InstantiateMsg { role_manager: "role_manager".to_string() }).unwrap();
+
+        let msg = ExecuteMsg::RoleChanged {
+            user: "mod".to_string(),
+            role: "MODERATOR_ROLE".to_string(),
+            scope: Some(tribe_scope(5)),
+            granted: false,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone()).unwrap_err();
+        let res = execute(deps.as_mut(), mock_env(), mock_info("role_manager", &[]), msg).unwrap();
+        assert_eq!("5", res.attributes[3].value);
+    }
+}